@@ -0,0 +1,178 @@
+// 테스트용 가짜 워커. playwright-worker.js 와 같은 줄 단위 JSON 프로토콜로
+// Hiworks 응답을 흉내내며, Chromium 없이 Rust 쪽을 검증할 때 사용합니다.
+//
+// 환경 변수
+//   MOCK_NOT_READY=1          ready 대신 { ready: false } 를 보냄
+//   MOCK_NOISY=1              응답마다 앞에 JSON 이 아닌 줄과 지난 id 응답을 섞음
+//   MOCK_CRASH_ONCE=<action>  해당 액션을 처음 받으면 응답 없이 종료
+//   MOCK_FAIL_STARTS=<n>      처음 n 번의 시작은 { ready: false } 를 보냄 (MOCK_STATE_DIR 에 횟수 기록)
+//   MOCK_STATE_DIR=<dir>      MOCK_CRASH_ONCE, MOCK_FAIL_STARTS 가 재시작 후에도 이어지도록 표시 파일을 둘 곳
+//   HIWORKS_CAPTURE_DIR=<dir> params.capture 가 있으면 실패한 요청의 가짜 화면/HTML 을 둘 곳
+//
+// 테스트 전용 액션
+//   __sleep  { ms }  ms 만큼 기다린 뒤 응답
+//   __hang           응답하지 않음
+//   __crash          응답 없이 종료
+//   __garbage        JSON 이 아닌 줄만 출력
+import { createInterface } from "readline";
+import { existsSync, mkdirSync, readdirSync, writeFileSync } from "fs";
+import { join } from "path";
+
+let checkInTime = null;
+let checkOutTime = null;
+let status = "업무";
+
+function respond(id, success, data, artifacts) {
+  if (process.env.MOCK_NOISY === "1") {
+    console.log("[playwright] stray log line");
+    console.log(JSON.stringify({ id: id + 100000, success: true, data: "stale" }));
+  }
+  console.log(JSON.stringify(artifacts ? { id, success, data, artifacts } : { id, success, data }));
+}
+
+function mockCapture(action, params) {
+  const dir = process.env.HIWORKS_CAPTURE_DIR;
+  if (!params?.capture || !dir) return null;
+  mkdirSync(dir, { recursive: true });
+  const artifacts = {
+    screenshot: join(dir, `${action}.png`),
+    html: join(dir, `${action}.html`),
+  };
+  writeFileSync(artifacts.screenshot, "");
+  writeFileSync(artifacts.html, "<!-- mock -->\n<html></html>");
+  return artifacts;
+}
+
+function now() {
+  const d = new Date();
+  return `${String(d.getHours()).padStart(2, "0")}:${String(d.getMinutes()).padStart(2, "0")}`;
+}
+
+function shouldCrash(action) {
+  if (process.env.MOCK_CRASH_ONCE !== action) return false;
+  const dir = process.env.MOCK_STATE_DIR;
+  if (!dir) return true;
+  const marker = join(dir, `crashed-${action}`);
+  if (existsSync(marker)) return false;
+  writeFileSync(marker, "");
+  return true;
+}
+
+// 시작할 때마다 start-<n> 파일을 남기고, MOCK_FAIL_STARTS 번째까지는 실패
+function failsToStart() {
+  const dir = process.env.MOCK_STATE_DIR;
+  if (!dir) return false;
+  const starts = readdirSync(dir).filter((f) => f.startsWith("start-")).length + 1;
+  writeFileSync(join(dir, `start-${starts}`), "");
+  return starts <= Number(process.env.MOCK_FAIL_STARTS ?? 0);
+}
+
+// 기간 안의 평일마다 09:00-18:00 근무
+function mockHistory(from, to) {
+  const days = [];
+  for (let d = new Date(`${from}T00:00:00Z`); d <= new Date(`${to}T00:00:00Z`); d.setUTCDate(d.getUTCDate() + 1)) {
+    const weekday = d.getUTCDay();
+    if (weekday === 0 || weekday === 6) continue;
+    days.push({
+      date: d.toISOString().slice(0, 10),
+      checkInTime: "09:00",
+      checkOutTime: "18:00",
+    });
+  }
+  return days;
+}
+
+// MOCK_LEAVE="2026-10-05:full,2026-10-06:morning"
+function mockLeave(from, to) {
+  return (process.env.MOCK_LEAVE ?? "")
+    .split(",")
+    .filter(Boolean)
+    .map((entry) => {
+      const [date, period] = entry.split(":");
+      return { date, kind: "연차", period: period ?? "full" };
+    })
+    .filter(({ date }) => date >= from && date <= to);
+}
+
+async function handleCommand({ id, action, params }) {
+  if (shouldCrash(action)) {
+    process.exit(1);
+  }
+
+  switch (action) {
+    case "openLogin":
+      return respond(id, true, "자동 로그인 완료");
+    case "checkIn":
+      if (checkInTime) {
+        return respond(id, true, {
+          message: `이미 출근 완료: ${checkInTime}`,
+          checkTime: checkInTime,
+        });
+      }
+      checkInTime = now();
+      return respond(id, true, {
+        message: `출근 완료: ${checkInTime}`,
+        checkTime: checkInTime,
+      });
+    case "checkOut":
+      checkOutTime = now();
+      return respond(id, true, {
+        message: `퇴근 완료: ${checkOutTime}`,
+        checkTime: checkOutTime,
+      });
+    case "setWork":
+      status = "업무";
+      return respond(id, true, "업무 상태로 변경됨");
+    case "goOut":
+      status = "외출";
+      return respond(id, true, "외출 처리 완료");
+    case "setMeeting":
+      status = "회의";
+      return respond(id, true, "회의 상태로 변경됨");
+    case "setOutwork":
+      status = "외근";
+      return respond(id, true, "외근 상태로 변경됨");
+    case "getStatus":
+      return respond(id, true, {
+        checkInTime: checkInTime ?? "",
+        checkOutTime: checkOutTime ?? "",
+        status,
+      });
+    case "getAttendanceHistory":
+      return respond(id, true, mockHistory(params?.from, params?.to));
+    case "getApprovedLeave":
+      return respond(id, true, mockLeave(params?.from, params?.to));
+    case "isLoggedIn":
+      return respond(id, true, true);
+    case "close":
+      return respond(id, true, "브라우저가 종료되었습니다");
+    case "__sleep":
+      await new Promise((r) => setTimeout(r, params?.ms ?? 0));
+      return respond(id, true, "잤음");
+    case "__hang":
+      return;
+    case "__crash":
+      process.exit(1);
+    case "__garbage":
+      console.log("<html>not json</html>");
+      return;
+    default:
+      return respond(id, false, `알 수 없는 명령: ${action}`, mockCapture(action, params));
+  }
+}
+
+const rl = createInterface({
+  input: process.stdin,
+  output: process.stdout,
+  terminal: false,
+});
+
+rl.on("line", async (line) => {
+  try {
+    await handleCommand(JSON.parse(line));
+  } catch (e) {
+    console.error(JSON.stringify({ error: e.message }));
+  }
+});
+
+console.log(JSON.stringify({ ready: process.env.MOCK_NOT_READY !== "1" && !failsToStart() }));
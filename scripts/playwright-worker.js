@@ -1,38 +1,60 @@
 import { chromium } from "playwright";
 import { createInterface } from "readline";
 import { join } from "path";
-import { existsSync, mkdirSync, readFileSync, writeFileSync } from "fs";
+import { existsSync, mkdirSync, readdirSync, unlinkSync, writeFileSync } from "fs";
 import { homedir } from "os";
 
 const CONFIG_DIR = join(homedir(), ".hiworks-commute");
-const CONFIG_FILE = join(CONFIG_DIR, "config.json");
-const USER_DATA_DIR = join(CONFIG_DIR, "browser-data");
+// 프로필마다 로그인 세션이 섞이지 않도록 앱이 경로를 지정함
+const USER_DATA_DIR = process.env.HIWORKS_BROWSER_DATA || join(CONFIG_DIR, "browser-data");
+const CAPTURE_DIR = process.env.HIWORKS_CAPTURE_DIR || join(CONFIG_DIR, "captures");
 
 if (!existsSync(CONFIG_DIR)) {
   mkdirSync(CONFIG_DIR, { recursive: true });
 }
 
-function loadConfig() {
-  if (existsSync(CONFIG_FILE)) {
-    return JSON.parse(readFileSync(CONFIG_FILE, "utf-8"));
-  }
-  return { companyUrl: null, username: null, password: null };
-}
-
-function saveConfig(cfg) {
-  writeFileSync(CONFIG_FILE, JSON.stringify(cfg, null, 2));
-}
-
 let browser = null;
 let context = null;
 let page = null;
-let config = loadConfig();
+// 설정은 Rust 쪽이 소유하며, 로그인이 필요한 명령마다 params 로 전달받음
+let config = { companyUrl: null, username: null };
+let sessionPassword = null;
 
-function respond(id, success, data) {
+function respond(id, success, data, artifacts) {
   const response = { id, success, data };
+  if (artifacts) response.artifacts = artifacts;
   console.log(JSON.stringify(response));
 }
 
+// 실패한 요청의 화면과 HTML 을 저장. 저장에 실패해도 원래 오류를 그대로 돌려줌
+async function captureFailure(action, capture) {
+  if (!capture || !page) return null;
+  try {
+    mkdirSync(CAPTURE_DIR, { recursive: true });
+    const stamp = new Date().toISOString().replace(/[:.]/g, "-");
+    const base = join(CAPTURE_DIR, `${stamp}-${action}`);
+    const artifacts = { screenshot: `${base}.png`, html: `${base}.html` };
+
+    await page.screenshot({ path: artifacts.screenshot, fullPage: true });
+    writeFileSync(artifacts.html, `<!-- ${page.url()} -->\n${await page.content()}`);
+    pruneCaptures(capture.keep ?? 20);
+    return artifacts;
+  } catch (e) {
+    console.error(`[capture] 실패 화면 저장 실패: ${e.message}`);
+    return null;
+  }
+}
+
+// 오래된 실패부터 지움 (실패 한 번에 .png, .html 두 파일)
+function pruneCaptures(keep) {
+  const files = readdirSync(CAPTURE_DIR)
+    .filter((f) => f.endsWith(".png") || f.endsWith(".html"))
+    .sort();
+  for (const file of files.slice(0, Math.max(0, files.length - keep * 2))) {
+    unlinkSync(join(CAPTURE_DIR, file));
+  }
+}
+
 async function initBrowser() {
   if (context) return;
 
@@ -71,7 +93,7 @@ async function isLoggedIn() {
 }
 
 async function performLogin() {
-  if (!config.password) {
+  if (!sessionPassword) {
     throw new Error(
       "비밀번호가 설정되지 않았습니다. 설정에서 비밀번호를 입력해주세요.",
     );
@@ -112,7 +134,7 @@ async function performLogin() {
     await passwordInput.waitFor({ state: "visible", timeout: 10000 });
   }
 
-  await passwordInput.fill(config.password);
+  await passwordInput.fill(sessionPassword);
 
   await page.waitForTimeout(500);
 
@@ -162,7 +184,10 @@ async function checkIn() {
   const isDisabled = await checkInBtn.getAttribute("disabled");
   if (isDisabled !== null) {
     const time = await checkInBtn.locator(".check-time").textContent();
-    return { success: true, message: `이미 출근 완료: ${time}` };
+    return {
+      success: true,
+      data: { message: `이미 출근 완료: ${time}`, checkTime: time },
+    };
   }
 
   await checkInBtn.click();
@@ -170,7 +195,10 @@ async function checkIn() {
 
   const time = await checkInBtn.locator(".check-time").textContent();
 
-  return { success: true, message: `출근 완료: ${time}` };
+  return {
+    success: true,
+    data: { message: `출근 완료: ${time}`, checkTime: time },
+  };
 }
 
 async function checkOut() {
@@ -188,7 +216,10 @@ async function checkOut() {
 
   const time = await checkOutBtn.locator(".check-time").textContent();
 
-  return { success: true, message: `퇴근 완료: ${time}` };
+  return {
+    success: true,
+    data: { message: `퇴근 완료: ${time}`, checkTime: time },
+  };
 }
 
 async function clickStatusButton(buttonText, successMessage, alreadyMessage) {
@@ -247,6 +278,113 @@ async function getStatus() {
   }
 }
 
+// 근태 > 개인 근무 기록 표에서 날짜별 출퇴근 시각을 읽습니다.
+// 첫 칸이 날짜(YYYY-MM-DD 또는 YYYY.MM.DD)인 행만 사용합니다.
+async function getAttendanceHistory(from, to) {
+  if (!from || !to) {
+    throw new Error("조회 기간이 없습니다");
+  }
+
+  await navigateToWorkPage();
+
+  const url = new URL("https://hr-work.office.hiworks.com/personal/history");
+  url.searchParams.set("start_date", from);
+  url.searchParams.set("end_date", to);
+  const rows = await readTableRows(url, ["출근", "퇴근"], "근무 기록");
+
+  const dated = rows.filter((cells) => /^\d{4}[-.]\d{2}[-.]\d{2}/.test(cells[0] ?? ""));
+  // "기록 없음" 같은 한 칸짜리 행이 아닌데 날짜를 하나도 못 읽었으면 표 구성이 다른 것
+  if (dated.length === 0 && rows.some((cells) => cells.length >= 3)) {
+    throw unsupported("근무 기록", "첫 칸에서 날짜를 읽을 수 없음");
+  }
+
+  const days = dated.map((cells) => ({
+    date: cells[0].slice(0, 10).replaceAll(".", "-"),
+    checkInTime: cells[1] ?? "",
+    checkOutTime: cells[2] ?? "",
+  }));
+
+  return { success: true, data: days };
+}
+
+function unsupported(feature, reason) {
+  return new Error(
+    `지원하지 않는 ${feature} 화면입니다 (${reason}). Hiworks 화면 구성이 바뀌었을 수 있습니다`,
+  );
+}
+
+// 주소와 표 구성은 화면을 보고 맞춘 것이라 Hiworks 가 바꾸면 달라질 수 있습니다.
+// 기대한 표가 아니면 빈 목록 대신 오류를 내서 "기록 없음" 과 구분합니다.
+async function readTableRows(url, headers, feature) {
+  const response = await page.goto(url.toString());
+  await page.waitForLoadState("networkidle");
+  if (!response || !response.ok()) {
+    throw unsupported(feature, `HTTP ${response ? response.status() : "응답 없음"}`);
+  }
+
+  const headerText = (await page.locator("table thead").allTextContents()).join(" ");
+  if (!headerText.trim()) {
+    throw unsupported(feature, "표를 찾을 수 없음");
+  }
+  const missing = headers.filter((h) => !headerText.includes(h));
+  if (missing.length > 0) {
+    throw unsupported(feature, `표에 ${missing.join(", ")} 열이 없음`);
+  }
+
+  return page
+    .locator("table tbody tr")
+    .evaluateAll((trs) =>
+      trs.map((tr) =>
+        Array.from(tr.querySelectorAll("td"), (td) => td.textContent.trim()),
+      ),
+    );
+}
+
+const DATE_PATTERN = /\d{4}[-.]\d{2}[-.]\d{2}/g;
+
+async function getApprovedLeave(from, to) {
+  if (!from || !to) {
+    throw new Error("조회 기간이 없습니다");
+  }
+
+  await navigateToWorkPage();
+
+  const url = new URL("https://hr-work.office.hiworks.com/personal/vacation/history");
+  url.searchParams.set("start_date", from);
+  url.searchParams.set("end_date", to);
+  const rows = await readTableRows(url, ["기간", "상태"], "휴가 내역");
+
+  const days = [];
+  for (const cells of rows) {
+    const text = cells.join(" ");
+    if (!text.includes("승인") || /미승인|반려|취소|대기/.test(text)) continue;
+
+    // 신청일 칸이 따로 있으므로 "~" 가 있는 기간 칸을 우선함
+    const periodCell =
+      cells.find((c) => c.includes("~") && c.match(DATE_PATTERN)) ??
+      [...cells].reverse().find((c) => c.match(DATE_PATTERN));
+    if (!periodCell) {
+      // 승인된 휴가인데 기간을 못 읽으면 조용히 빼지 않고 알림
+      throw unsupported("휴가 내역", "승인된 휴가의 기간을 읽을 수 없음");
+    }
+
+    const dates = periodCell.match(DATE_PATTERN).map((d) => d.replaceAll(".", "-"));
+    const start = dates[0];
+    const end = dates[dates.length - 1];
+    const kind = cells.find((c) => /휴가|연차|반차|병가|경조|공가/.test(c)) ?? "휴가";
+    const period = text.includes("오전") ? "morning" : text.includes("오후") ? "afternoon" : "full";
+
+    for (let d = new Date(`${start}T00:00:00Z`); d <= new Date(`${end}T00:00:00Z`); d.setUTCDate(d.getUTCDate() + 1)) {
+      const date = d.toISOString().slice(0, 10);
+      if (date >= from && date <= to) {
+        days.push({ date, kind, period });
+      }
+    }
+  }
+
+  return { success: true, data: days };
+}
+
 async function openLoginBrowser() {
   if (!config.companyUrl) {
     throw new Error("회사 URL이 설정되지 않았습니다");
@@ -256,7 +394,7 @@ async function openLoginBrowser() {
   await page.goto(config.companyUrl);
   await page.waitForLoadState("networkidle");
 
-  if (config.username && config.password) {
+  if (config.username && sessionPassword) {
     try {
       await performLogin();
       return { success: true, message: "자동 로그인 완료" };
@@ -280,36 +418,6 @@ async function openLoginBrowser() {
   };
 }
 
-function setCompanyUrl(url) {
-  config.companyUrl = url;
-  saveConfig(config);
-  return { success: true, message: "회사 URL이 설정되었습니다" };
-}
-
-function getCompanyUrl() {
-  return { success: true, data: config.companyUrl };
-}
-
-function setUsername(username) {
-  config.username = username;
-  saveConfig(config);
-  return { success: true, message: "아이디가 저장되었습니다" };
-}
-
-function getUsername() {
-  return { success: true, data: config.username };
-}
-
-function setPassword(password) {
-  config.password = password;
-  saveConfig(config);
-  return { success: true, message: "비밀번호가 저장되었습니다" };
-}
-
-function hasPassword() {
-  return { success: true, data: !!config.password };
-}
-
 async function closeBrowser() {
   if (context) {
     await context.close();
@@ -322,29 +430,17 @@ async function closeBrowser() {
 
 async function handleCommand(cmd) {
   const { id, action, params } = cmd;
+  config = {
+    companyUrl: params?.companyUrl ?? null,
+    username: params?.username ?? null,
+  };
+  sessionPassword = params?.password ?? null;
+  const capture = params?.capture ?? null;
 
   try {
     let result;
 
     switch (action) {
-      case "setCompanyUrl":
-        result = setCompanyUrl(params.url);
-        break;
-      case "getCompanyUrl":
-        result = getCompanyUrl();
-        break;
-      case "setUsername":
-        result = setUsername(params.username);
-        break;
-      case "getUsername":
-        result = getUsername();
-        break;
-      case "setPassword":
-        result = setPassword(params.password);
-        break;
-      case "hasPassword":
-        result = hasPassword();
-        break;
       case "openLogin":
         result = await openLoginBrowser();
         break;
@@ -369,6 +465,12 @@ async function handleCommand(cmd) {
       case "getStatus":
         result = await getStatus();
         break;
+      case "getAttendanceHistory":
+        result = await getAttendanceHistory(params?.from, params?.to);
+        break;
+      case "getApprovedLeave":
+        result = await getApprovedLeave(params?.from, params?.to);
+        break;
       case "isLoggedIn":
         result = { success: true, data: await isLoggedIn() };
         break;
@@ -387,9 +489,12 @@ async function handleCommand(cmd) {
     } else {
       responseData = result;
     }
-    respond(id, result.success !== false, responseData);
+    const success = result.success !== false;
+    respond(id, success, responseData, success ? null : await captureFailure(action, capture));
   } catch (e) {
-    respond(id, false, e.message);
+    respond(id, false, e.message, await captureFailure(action, capture));
+  } finally {
+    sessionPassword = null;
   }
 }
 
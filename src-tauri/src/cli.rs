@@ -0,0 +1,699 @@
+use crate::config::{self, ConfigStore};
+use crate::control;
+use crate::credentials::{self, CredentialStore, KeyringStore, CALENDAR_ACCOUNT, DEFAULT_ACCOUNT};
+use crate::diagnostics;
+use crate::history::{self, HistoryEntry};
+use crate::holidays;
+use crate::journal::Journal;
+use crate::logging;
+use crate::playwright::{PlaywrightError, PlaywrightWorker};
+use crate::profile::{self, AppLock, Profile, ProfileCredentials, Profiles};
+use crate::protocol::{ActionReply, AttendanceStatus, FailureCapture, WorkStatus, WorkerRequest};
+use crate::worker::request_params;
+use chrono::Local;
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+const USAGE: &str = "\
+사용법: hiworks-commute [--json] [--verbose] [--profile <ID>] <명령>
+
+명령:
+  check-in                       출근
+  check-out                      퇴근
+  status                         오늘 출퇴근 시각과 근무 상태
+  set-status <work|out|meeting|outwork>
+                                 근무 상태 변경
+  config get [url|username]      설정 보기
+  config set url <URL>           회사 로그인 URL 설정
+  config set username <아이디>   아이디 설정
+  config set password            표준 입력으로 받은 비밀번호를 키체인에 저장
+  config set calendar-password   표준 입력으로 받은 CalDAV 비밀번호를 키체인에 저장
+  profiles                       프로필 목록 (* 는 트레이 앱이 쓰는 프로필)
+  holidays import <파일>         공휴일 JSON 파일 반영 (파일에 있는 연도를 교체)
+  diagnose                       설치, 설정, 로그인 상태를 확인하고 지원 요청용 zip 저장
+
+옵션:
+  --json                         결과를 JSON 으로 출력
+  -v, --verbose                  로그를 표준 에러에도 출력 (파일에는 항상 남김)
+  --profile <ID>                 지정한 프로필로 실행 (기본값: 트레이 앱이 쓰는 프로필)
+
+종료 코드:
+  0 성공, 1 Hiworks 처리 실패, 2 잘못된 사용법, 3 설정 오류,
+  4 응답 시간 초과, 5 워커 비정상 종료, 6 node/워커 스크립트 실행 불가,
+  7 워커 응답 형식 오류, 8 트레이 앱이 실행 중이고 제어 API 가 꺼져 있음
+
+트레이 앱이 실행 중이면 출퇴근, 상태 변경, 상태 조회는 앱의 로컬 제어 API 로 보냅니다.";
+
+/// 종료 코드
+pub mod exit {
+    pub const OK: i32 = 0;
+    pub const FAILED: i32 = 1;
+    pub const USAGE: i32 = 2;
+    pub const CONFIG: i32 = 3;
+    pub const TIMEOUT: i32 = 4;
+    pub const CRASHED: i32 = 5;
+    pub const UNAVAILABLE: i32 = 6;
+    pub const PROTOCOL: i32 = 7;
+    pub const APP_RUNNING: i32 = 8;
+}
+
+#[derive(Error, Debug)]
+pub enum CliError {
+    #[error("{0}")]
+    Usage(String),
+    #[error("{0}")]
+    Config(String),
+    #[error(transparent)]
+    Worker(#[from] PlaywrightError),
+    /// 트레이 앱이 브라우저 데이터를 쓰고 있는데 제어 API 로 보낼 수 없음
+    #[error("트레이 앱이 이 프로필을 쓰고 있습니다. 트레이 앱을 끄거나 설정의 control.enabled 로 로컬 제어 API 를 켜고 앱을 다시 시작해주세요")]
+    AppRunning,
+    /// 트레이 앱이 처리하다 실패함
+    #[error("{0}")]
+    App(String),
+}
+
+impl CliError {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Usage(_) => exit::USAGE,
+            CliError::Config(_) => exit::CONFIG,
+            CliError::AppRunning => exit::APP_RUNNING,
+            CliError::App(_) => exit::FAILED,
+            CliError::Worker(e) => match e {
+                PlaywrightError::ResponseError(_) | PlaywrightError::Captured { .. } => {
+                    exit::FAILED
+                }
+                PlaywrightError::Timeout { .. } => exit::TIMEOUT,
+                PlaywrightError::Crashed | PlaywrightError::NotRunning => exit::CRASHED,
+                PlaywrightError::SpawnError(_)
+                | PlaywrightError::ScriptNotFound
+                | PlaywrightError::NodeNotFound => exit::UNAVAILABLE,
+                PlaywrightError::JsonError(_) => exit::PROTOCOL,
+            },
+        }
+    }
+
+    pub fn capture(&self) -> Option<&FailureCapture> {
+        match self {
+            CliError::Worker(e) => e.capture(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum ConfigKey {
+    Url,
+    Username,
+    Password,
+    CalendarPassword,
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Help,
+    Run(WorkerRequest),
+    Status,
+    Profiles,
+    ImportHolidays(String),
+    Diagnose,
+    ConfigGet(Option<ConfigKey>),
+    ConfigSet(ConfigKey, Option<String>),
+}
+
+fn parse_key(key: &str) -> Result<ConfigKey, CliError> {
+    match key {
+        "url" => Ok(ConfigKey::Url),
+        "username" => Ok(ConfigKey::Username),
+        "password" => Ok(ConfigKey::Password),
+        "calendar-password" => Ok(ConfigKey::CalendarPassword),
+        _ => Err(CliError::Usage(format!("알 수 없는 설정 항목: {}", key))),
+    }
+}
+
+fn parse_status(status: &str) -> Result<WorkStatus, CliError> {
+    match status {
+        "work" => Ok(WorkStatus::Work),
+        "out" => Ok(WorkStatus::Out),
+        "meeting" => Ok(WorkStatus::Meeting),
+        "outwork" => Ok(WorkStatus::Outwork),
+        _ => Err(CliError::Usage(format!("알 수 없는 근무 상태: {}", status))),
+    }
+}
+
+/// 명령 앞뒤 어디에나 올 수 있는 옵션
+#[derive(Debug, Default, PartialEq)]
+struct Options<'a> {
+    json: bool,
+    /// 로그를 표준 에러에도 출력
+    verbose: bool,
+    profile: Option<&'a str>,
+}
+
+/// 옵션과 나머지 인자를 나눕니다.
+fn split_options<'a>(args: &[&'a str]) -> Result<(Options<'a>, Vec<&'a str>), CliError> {
+    let mut options = Options::default();
+    let mut rest = Vec::new();
+    let mut args = args.iter();
+    while let Some(&arg) = args.next() {
+        match arg {
+            "--json" => options.json = true,
+            "--verbose" | "-v" => options.verbose = true,
+            "--profile" => {
+                let id = args.next().ok_or_else(|| {
+                    CliError::Usage("--profile 뒤에 프로필 ID 가 필요합니다".to_string())
+                })?;
+                options.profile = Some(id);
+            }
+            _ => match arg.strip_prefix("--profile=") {
+                Some(id) => options.profile = Some(id),
+                None => rest.push(arg),
+            },
+        }
+    }
+    Ok((options, rest))
+}
+
+/// 옵션을 뺀 인자로 명령을 읽습니다.
+fn parse(args: &[&str]) -> Result<Command, CliError> {
+    let command = match args {
+        [] | ["help" | "-h" | "--help"] => Command::Help,
+        ["check-in"] => Command::Run(WorkerRequest::CheckIn),
+        ["check-out"] => Command::Run(WorkerRequest::CheckOut),
+        ["status"] => Command::Status,
+        ["profiles"] => Command::Profiles,
+        ["holidays", "import", path] => Command::ImportHolidays(path.to_string()),
+        ["diagnose"] => Command::Diagnose,
+        ["set-status", status] => Command::Run(WorkerRequest::SetStatus(parse_status(status)?)),
+        ["config", "get"] => Command::ConfigGet(None),
+        ["config", "get", key] => match parse_key(key)? {
+            ConfigKey::Password | ConfigKey::CalendarPassword => {
+                return Err(CliError::Usage("비밀번호는 조회할 수 없습니다".to_string()))
+            }
+            key => Command::ConfigGet(Some(key)),
+        },
+        ["config", "set", key @ ("password" | "calendar-password")] => {
+            Command::ConfigSet(parse_key(key)?, None)
+        }
+        ["config", "set", "password" | "calendar-password", _] => {
+            return Err(CliError::Usage(
+                "비밀번호는 인자 대신 표준 입력으로 전달해주세요".to_string(),
+            ))
+        }
+        ["config", "set", key, value] => {
+            Command::ConfigSet(parse_key(key)?, Some(value.to_string()))
+        }
+        _ => return Err(CliError::Usage(format!("알 수 없는 명령: {}", args.join(" ")))),
+    };
+    Ok(command)
+}
+
+/// 트레이 앱 없이 워커를 직접 띄워 명령 하나를 처리합니다.
+struct Session {
+    worker: PlaywrightWorker,
+    config: ConfigStore,
+    credentials: ProfileCredentials,
+    history: Journal,
+}
+
+impl Session {
+    fn open(profile: &Profile) -> Result<Self, CliError> {
+        let legacy_config = config::config_path();
+        if let Err(e) = credentials::migrate_plaintext_password(&legacy_config, &KeyringStore) {
+            tracing::warn!(error = %e, "비밀번호 이전 실패");
+        }
+
+        let config = load_config(profile)?;
+
+        let mut worker = PlaywrightWorker::new();
+        worker.set_browser_data(profile.browser_data_dir());
+        worker.set_capture_dir(profile.captures_dir());
+        for (action, secs) in &config.get().timeouts {
+            worker.set_timeout(action, Duration::from_secs(*secs));
+        }
+
+        Ok(Self {
+            worker,
+            config,
+            credentials: ProfileCredentials::new(Arc::new(KeyringStore), profile),
+            history: history::history_journal(profile),
+        })
+    }
+
+    fn request<T: DeserializeOwned>(&mut self, request: WorkerRequest) -> Result<T, CliError> {
+        let config = self.config.get();
+        if request.needs_login() && !config.has_company_url() {
+            return Err(CliError::Config(
+                "회사 URL이 설정되지 않았습니다 (hiworks-commute config set url <URL>)".to_string(),
+            ));
+        }
+
+        let params = request_params(request, &config, &self.credentials)?;
+        let value = self.worker.send_command(request.action(), params)?;
+        Ok(serde_json::from_value(value).map_err(PlaywrightError::from)?)
+    }
+
+    /// 출퇴근/상태 변경은 트레이 앱과 같은 출퇴근 기록에 남깁니다.
+    fn run(&mut self, request: WorkerRequest) -> Result<ActionReply, CliError> {
+        let requested_at = Local::now();
+        let result = self.request::<ActionReply>(request);
+
+        // 설정 오류로 워커에 보내지 못한 요청은 남기지 않음
+        if !matches!(result, Err(CliError::Config(_))) {
+            let entry = HistoryEntry::from_result(requested_at, request, &result)
+                .with_capture(result.as_ref().err().and_then(CliError::capture));
+            if let Err(e) = self.history.append(&entry) {
+                tracing::warn!(error = %e, "출퇴근 기록 저장 실패");
+            }
+        }
+
+        result
+    }
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        // 브라우저를 정상적으로 닫아 세션 파일이 깨지지 않게 함
+        if self.worker.is_alive() {
+            let _ = self.worker.send_command(WorkerRequest::Close.action(), json!({}));
+        }
+        let _ = self.worker.stop();
+    }
+}
+
+/// 트레이 앱이 프로필을 쓰고 있으면 Chromium 이 브라우저 데이터를 잠그고 있어
+/// 워커를 따로 띄울 수 없으므로 앱의 제어 서버로 보냅니다.
+/// 앱이 실행 중이 아니면 `None` 이라 직접 워커를 띄웁니다.
+fn via_app(
+    profile: &Profile,
+    method: &str,
+    path: Option<&str>,
+) -> Result<Option<serde_json::Value>, CliError> {
+    if !AppLock::is_held(&profile.app_lock_path()) {
+        return Ok(None);
+    }
+    let control = load_config(profile)?.get().control;
+    let Some(path) = path.filter(|_| control.enabled) else {
+        return Err(CliError::AppRunning);
+    };
+
+    match control::call(&control, method, path) {
+        Ok((200, body)) => Ok(Some(body)),
+        Ok((_, body)) => Err(CliError::App(
+            body["error"]
+                .as_str()
+                .unwrap_or("트레이 앱이 요청을 처리하지 못했습니다")
+                .to_string(),
+        )),
+        Err(e) => {
+            tracing::warn!(error = %e, "트레이 앱의 제어 서버에 연결할 수 없습니다");
+            Err(CliError::AppRunning)
+        }
+    }
+}
+
+/// 제어 서버는 `AttendanceStatus` 를 직렬화한 형식으로 보냄 (워커 응답과 다름)
+fn status_from_app(body: &serde_json::Value) -> Result<AttendanceStatus, CliError> {
+    let status = &body["status"];
+    let time =
+        |key: &str| serde_json::from_value(status[key].clone()).map_err(PlaywrightError::from);
+    Ok(AttendanceStatus {
+        check_in: time("checkIn")?,
+        check_out: time("checkOut")?,
+        status: status["status"].as_str().map(str::to_string),
+    })
+}
+
+fn format_time(time: Option<chrono::NaiveTime>) -> String {
+    time.map(|t| t.format("%H:%M").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn load_config(profile: &Profile) -> Result<ConfigStore, CliError> {
+    ConfigStore::load(profile.config_path()).map_err(|e| CliError::Config(e.to_string()))
+}
+
+fn load_profiles() -> Result<Profiles, CliError> {
+    Profiles::load(&profile::profiles_path()).map_err(|e| CliError::Config(e.to_string()))
+}
+
+/// `--profile` 로 고른 프로필, 없으면 트레이 앱이 쓰는 프로필
+fn resolve_profile(id: Option<&str>) -> Result<Profile, CliError> {
+    let profiles = load_profiles()?;
+    match id {
+        Some(id) => profiles
+            .get(id)
+            .cloned()
+            .map_err(|e| CliError::Config(e.to_string())),
+        None => Ok(profiles.active().clone()),
+    }
+}
+
+fn execute(command: Command, json: bool, profile: Option<&str>) -> Result<(), CliError> {
+    match command {
+        Command::Help => println!("{}", USAGE),
+        Command::Profiles => {
+            let profiles = load_profiles()?;
+            if json {
+                println!("{}", json!(profiles));
+            } else {
+                for p in &profiles.profiles {
+                    let marker = if p.id == profiles.active { "*" } else { " " };
+                    println!("{} {}\t{}", marker, p.id, p.name);
+                }
+            }
+        }
+        Command::ImportHolidays(path) => {
+            let years = holidays::import(Path::new(&path))
+                .map_err(|e| CliError::Config(e.to_string()))?;
+            if json {
+                println!("{}", json!({ "ok": true, "years": years }));
+            } else {
+                let years: Vec<String> = years.iter().map(i32::to_string).collect();
+                println!("{}년 공휴일을 반영했습니다", years.join(", "));
+            }
+        }
+        Command::Diagnose => {
+            // 중간에 실패해도 멈추지 않고 실패한 항목으로 남김
+            let (profile, profile_check) = diagnostics::profile_check(resolve_profile(profile));
+            let credentials = ProfileCredentials::new(Arc::new(KeyringStore), &profile);
+            let mut checks = vec![profile_check];
+            checks.extend(diagnostics::environment_checks(&profile, &credentials));
+            checks.push(diagnostics::startup_check());
+            if AppLock::is_held(&profile.app_lock_path()) {
+                checks.push(diagnostics::app_running_check());
+            } else {
+                // 브라우저 세션은 재시작해도 남으므로 트레이 앱과 같이 세션부터 확인하고,
+                // 로그인되어 있지 않을 때만 자동 로그인을 해 봄
+                let (logged_in, status) = match Session::open(&profile) {
+                    Ok(mut session) => {
+                        let logged_in = session.request(WorkerRequest::IsLoggedIn);
+                        let status = match logged_in {
+                            Ok(false) if session.config.get().has_company_url() => {
+                                Some(session.request(WorkerRequest::GetStatus))
+                            }
+                            _ => None,
+                        };
+                        (logged_in, status)
+                    }
+                    Err(e) => (Err(e), None),
+                };
+                checks.push(diagnostics::login_check(logged_in, status));
+            }
+
+            let report = diagnostics::Report::new(&profile, checks);
+            let path = diagnostics::write_bundle(&report, &profile, &logging::export_dir());
+            if json {
+                let path = path.as_ref().ok();
+                println!("{}", json!({ "report": report, "path": path }));
+            } else {
+                print!("{}", report.to_text());
+            }
+            let path = path.map_err(|e| CliError::Config(format!("진단 묶음 저장 실패: {}", e)))?;
+            if !json {
+                println!("\n{} 에 저장했습니다", path.display());
+            }
+        }
+        Command::Run(request) => {
+            let profile = resolve_profile(profile)?;
+            // 트레이 앱을 거치면 출퇴근 기록은 앱이 남김
+            let reply = match via_app(&profile, "POST", control::action_path(request))? {
+                Some(body) => ActionReply {
+                    message: body["message"].as_str().unwrap_or_default().to_string(),
+                    check_time: None,
+                },
+                None => Session::open(&profile)?.run(request)?,
+            };
+            if json {
+                println!(
+                    "{}",
+                    json!({
+                        "ok": true,
+                        "action": request.action(),
+                        "message": reply.message,
+                        "checkTime": reply.check_time,
+                    })
+                );
+            } else {
+                println!("{}", reply.message);
+            }
+        }
+        Command::Status => {
+            let profile = resolve_profile(profile)?;
+            let status: AttendanceStatus = match via_app(&profile, "GET", Some("/status"))? {
+                Some(body) => status_from_app(&body)?,
+                None => Session::open(&profile)?.request(WorkerRequest::GetStatus)?,
+            };
+            if json {
+                println!("{}", serde_json::to_string(&status).map_err(PlaywrightError::from)?);
+            } else {
+                println!("출근: {}", format_time(status.check_in));
+                println!("퇴근: {}", format_time(status.check_out));
+                println!("상태: {}", status.status.as_deref().unwrap_or("-"));
+            }
+        }
+        Command::ConfigGet(key) => {
+            let profile = resolve_profile(profile)?;
+            let config = load_config(&profile)?.get();
+            match key {
+                Some(key) => {
+                    let value = match key {
+                        ConfigKey::Url => config.company_url,
+                        _ => config.username,
+                    };
+                    if json {
+                        println!("{}", json!(value));
+                    } else {
+                        println!("{}", value.unwrap_or_default());
+                    }
+                }
+                None => {
+                    let has_password = ProfileCredentials::new(Arc::new(KeyringStore), &profile)
+                        .get(DEFAULT_ACCOUNT)
+                        .map(|p| p.is_some())
+                        .unwrap_or(false);
+                    if json {
+                        println!(
+                            "{}",
+                            json!({
+                                "url": config.company_url,
+                                "username": config.username,
+                                "hasPassword": has_password,
+                            })
+                        );
+                    } else {
+                        println!("url: {}", config.company_url.unwrap_or_default());
+                        println!("username: {}", config.username.unwrap_or_default());
+                        println!("password: {}", if has_password { "(저장됨)" } else { "-" });
+                    }
+                }
+            }
+        }
+        Command::ConfigSet(key, value) => {
+            let message = set_config(&resolve_profile(profile)?, key, value)?;
+            if json {
+                println!("{}", json!({ "ok": true, "message": message }));
+            } else {
+                println!("{}", message);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn set_config(
+    profile: &Profile,
+    key: ConfigKey,
+    value: Option<String>,
+) -> Result<&'static str, CliError> {
+    let config_error = |e: config::ConfigError| CliError::Config(e.to_string());
+
+    match (key, value) {
+        (ConfigKey::Url, Some(url)) => {
+            let url = config::validate_company_url(&url).map_err(config_error)?;
+            let store = load_config(profile)?;
+            store
+                .update(|c| c.company_url = Some(url))
+                .map_err(config_error)?;
+            Ok("회사 URL이 설정되었습니다")
+        }
+        (ConfigKey::Username, Some(username)) => {
+            let store = load_config(profile)?;
+            store
+                .update(|c| c.username = Some(username))
+                .map_err(config_error)?;
+            Ok("아이디가 저장되었습니다")
+        }
+        (key @ (ConfigKey::Password | ConfigKey::CalendarPassword), _) => {
+            let mut password = String::new();
+            std::io::stdin()
+                .read_line(&mut password)
+                .map_err(|e| CliError::Usage(e.to_string()))?;
+            let password = password.trim_end_matches(['\r', '\n']);
+            if password.is_empty() {
+                return Err(CliError::Usage("비밀번호가 비어 있습니다".to_string()));
+            }
+            let account = match key {
+                ConfigKey::CalendarPassword => CALENDAR_ACCOUNT,
+                _ => DEFAULT_ACCOUNT,
+            };
+            ProfileCredentials::new(Arc::new(KeyringStore), profile)
+                .set(account, password)
+                .map_err(|e| CliError::Config(e.to_string()))?;
+            Ok("비밀번호가 저장되었습니다")
+        }
+        (_, None) => Err(CliError::Usage("설정할 값이 없습니다".to_string())),
+    }
+}
+
+/// 명령줄 인자 (프로그램 이름 제외) 를 처리하고 종료 코드를 돌려줍니다.
+pub fn run(args: &[String]) -> i32 {
+    logging::init(args.iter().any(|a| a == "--verbose" || a == "-v"));
+    let json = args.iter().any(|a| a == "--json");
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = split_options(&args).and_then(|(options, rest)| {
+        parse(&rest).and_then(|command| execute(command, options.json, options.profile))
+    });
+    match result {
+        Ok(()) => exit::OK,
+        Err(e) => {
+            let code = e.exit_code();
+            if json {
+                println!(
+                    "{}",
+                    json!({
+                        "ok": false,
+                        "error": e.to_string(),
+                        "exitCode": code,
+                        "capture": e.capture(),
+                    })
+                );
+            } else {
+                eprintln!("오류: {}", e);
+                if let Some(capture) = e.capture() {
+                    eprintln!("실패 화면: {}", capture.screenshot.display());
+                    eprintln!("페이지 HTML: {}", capture.html.display());
+                }
+                if code == exit::USAGE {
+                    eprintln!("\n{}", USAGE);
+                }
+            }
+            code
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(parse(&[]).unwrap(), Command::Help);
+        assert_eq!(
+            parse(&["check-in"]).unwrap(),
+            Command::Run(WorkerRequest::CheckIn)
+        );
+        assert_eq!(
+            parse(&["set-status", "meeting"]).unwrap(),
+            Command::Run(WorkerRequest::SetStatus(WorkStatus::Meeting))
+        );
+        assert_eq!(parse(&["status"]).unwrap(), Command::Status);
+        assert_eq!(
+            parse(&["config", "set", "url", "https://login.office.hiworks.com/acme"]).unwrap(),
+            Command::ConfigSet(
+                ConfigKey::Url,
+                Some("https://login.office.hiworks.com/acme".to_string())
+            )
+        );
+        assert_eq!(
+            parse(&["config", "set", "password"]).unwrap(),
+            Command::ConfigSet(ConfigKey::Password, None)
+        );
+        assert_eq!(
+            parse(&["config", "set", "calendar-password"]).unwrap(),
+            Command::ConfigSet(ConfigKey::CalendarPassword, None)
+        );
+        assert_eq!(parse(&["profiles"]).unwrap(), Command::Profiles);
+        assert_eq!(
+            parse(&["holidays", "import", "holidays-2028.json"]).unwrap(),
+            Command::ImportHolidays("holidays-2028.json".to_string())
+        );
+        assert_eq!(parse(&["diagnose"]).unwrap(), Command::Diagnose);
+    }
+
+    #[test]
+    fn splits_options() {
+        let (options, rest) =
+            split_options(&["--profile", "acme", "check-in", "--json", "-v"]).unwrap();
+        assert_eq!(
+            options,
+            Options {
+                json: true,
+                verbose: true,
+                profile: Some("acme"),
+            }
+        );
+        assert_eq!(rest, vec!["check-in"]);
+
+        let (options, rest) = split_options(&["status", "--profile=sub"]).unwrap();
+        assert_eq!(options.profile, Some("sub"));
+        assert_eq!(rest, vec!["status"]);
+
+        assert_eq!(
+            split_options(&["check-in", "--profile"]).unwrap_err().exit_code(),
+            exit::USAGE
+        );
+    }
+
+    #[test]
+    fn rejects_bad_usage() {
+        for args in [
+            &["check"][..],
+            &["set-status", "lunch"],
+            &["config", "set", "password", "hunter2"],
+            &["config", "get", "password"],
+            &["config", "set", "calendar-password", "hunter2"],
+            &["profiles", "acme"],
+            &["holidays", "import"],
+            &["holidays"],
+            &["diagnose", "now"],
+        ] {
+            assert_eq!(parse(args).unwrap_err().exit_code(), exit::USAGE, "{:?}", args);
+        }
+    }
+
+    #[test]
+    fn maps_worker_errors_to_exit_codes() {
+        let code = |e: PlaywrightError| CliError::from(e).exit_code();
+        assert_eq!(code(PlaywrightError::ResponseError("x".into())), exit::FAILED);
+        assert_eq!(
+            code(PlaywrightError::Timeout {
+                action: "checkIn".into(),
+                timeout: Duration::from_secs(1),
+            }),
+            exit::TIMEOUT
+        );
+        assert_eq!(code(PlaywrightError::Crashed), exit::CRASHED);
+        assert_eq!(code(PlaywrightError::NodeNotFound), exit::UNAVAILABLE);
+        assert_eq!(CliError::AppRunning.exit_code(), exit::APP_RUNNING);
+        assert_eq!(CliError::App("x".into()).exit_code(), exit::FAILED);
+    }
+
+    #[test]
+    fn reads_status_from_app() {
+        let status = AttendanceStatus {
+            check_in: chrono::NaiveTime::from_hms_opt(8, 55, 0),
+            check_out: None,
+            status: Some("업무".to_string()),
+        };
+        let body = json!({ "ok": true, "status": status });
+        assert_eq!(status_from_app(&body).unwrap(), status);
+    }
+}
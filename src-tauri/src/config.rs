@@ -0,0 +1,585 @@
+use chrono::{NaiveDate, NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use thiserror::Error;
+
+/// 현재 설정 파일 스키마 버전
+pub const CONFIG_VERSION: u32 = 1;
+
+/// 회사 로그인 페이지가 있어야 하는 호스트
+const LOGIN_HOST: &str = "login.office.hiworks.com";
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("설정 파일 오류: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("설정 파일 파싱 오류: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("올바르지 않은 URL: {0}")]
+    InvalidUrl(String),
+    #[error("지원하지 않는 설정 버전: {0}")]
+    UnsupportedVersion(u32),
+    #[error("올바르지 않은 네트워크 대역: {0}")]
+    InvalidSubnet(String),
+}
+
+/// `~/.hiworks-commute/config.json` 에 저장되는 앱 설정
+///
+/// 이전 버전 워커가 쓰던 파일과 호환되도록 camelCase 키를 사용합니다.
+/// 버전이 없는 파일은 0 으로 읽혀 현재 버전으로 올려 저장됩니다.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Config {
+    pub version: u32,
+    pub company_url: Option<String>,
+    pub username: Option<String>,
+    /// 액션별 응답 대기 시간 (초)
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub timeouts: HashMap<String, u64>,
+    pub schedule: ScheduleConfig,
+    /// 트레이 상태를 서버에서 다시 읽어오는 간격 (분), 0 이면 끔
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_poll_minutes: Option<u64>,
+    pub report: ReportConfig,
+    pub control: ControlConfig,
+    pub network: NetworkConfig,
+    pub idle: IdleConfig,
+    pub calendar: CalendarConfig,
+    pub day_off: DayOffConfig,
+    pub check_out_reminder: CheckOutReminderConfig,
+    pub check_in_reminder: CheckInReminderConfig,
+    pub capture: CaptureConfig,
+}
+
+/// 자동 출퇴근 예약
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScheduleConfig {
+    pub enabled: bool,
+    /// 요일별 출근 시각 (`{"Mon": "09:00"}`), 없는 요일은 건너뜀
+    pub check_in: HashMap<Weekday, NaiveTime>,
+    /// 요일별 퇴근 시각
+    pub check_out: HashMap<Weekday, NaiveTime>,
+    /// 예약 시각 앞뒤로 무작위로 흔들 범위 (분)
+    pub jitter_minutes: u32,
+    /// 예약을 건너뛸 날짜
+    pub holidays: Vec<NaiveDate>,
+}
+
+/// 근무 시간 보고서 기준
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ReportConfig {
+    /// 하루 소정 근로 시간, 넘으면 연장 근로
+    pub daily_standard_hours: u32,
+    /// 주 소정 근로 시간, 넘으면 연장 근로
+    pub weekly_standard_hours: u32,
+    /// 주 최대 근로 시간 (연장 포함)
+    pub weekly_limit_hours: u32,
+    /// 출퇴근 사이 시간에서 법정 휴게 시간 (4시간 근로에 30분, 8시간에 1시간) 을 뺄지 여부
+    pub deduct_breaks: bool,
+}
+
+impl Default for ReportConfig {
+    fn default() -> Self {
+        Self {
+            daily_standard_hours: 8,
+            weekly_standard_hours: 40,
+            weekly_limit_hours: 52,
+            deduct_breaks: true,
+        }
+    }
+}
+
+/// 스크립트에서 출퇴근을 실행하는 로컬 제어 서버 (바꾸면 앱을 다시 시작해야 함)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ControlConfig {
+    pub enabled: bool,
+    /// `127.0.0.1` 에서만 받는 HTTP 포트
+    pub port: u16,
+    /// `control.sock` Unix 소켓도 열지 여부 (macOS/Linux)
+    pub unix_socket: bool,
+}
+
+impl Default for ControlConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 17321,
+            unix_socket: true,
+        }
+    }
+}
+
+/// 사무실 네트워크에 연결되면 자동 출근
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NetworkConfig {
+    pub enabled: bool,
+    /// 사무실 Wi-Fi 이름
+    pub office_ssids: Vec<String>,
+    /// 사무실 IP 대역 (`10.20.0.0/16`)
+    pub office_subnets: Vec<String>,
+    /// 네트워크를 확인하는 간격 (초)
+    pub poll_seconds: u64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            office_ssids: Vec::new(),
+            office_subnets: Vec::new(),
+            poll_seconds: 30,
+        }
+    }
+}
+
+/// 화면 잠금/유휴 시 외출로 전환
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct IdleConfig {
+    pub enabled: bool,
+    /// 잠금 또는 입력 없음이 이만큼 이어지면 외출로 바꿈 (분)
+    pub threshold_minutes: u64,
+    /// 세션 상태 변경 시그널을 받을 수 없을 때 확인하는 간격 (초)
+    pub poll_seconds: u64,
+}
+
+impl Default for IdleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_minutes: 30,
+            poll_seconds: 15,
+        }
+    }
+}
+
+/// 캘린더의 회의 시간에 맞춰 회의/업무 상태로 전환
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CalendarConfig {
+    pub enabled: bool,
+    /// `.ics` 파일 경로 또는 CalDAV/구독 주소 (`https://`, `webcal://`)
+    pub source: Option<String>,
+    /// 거절한 회의를 찾을 때 쓰는 참석자 이메일
+    pub email: Option<String>,
+    /// CalDAV 아이디, 비밀번호는 키체인에 저장
+    pub username: Option<String>,
+    /// 일정을 다시 읽어오는 간격 (분)
+    pub refresh_minutes: u64,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            source: None,
+            email: None,
+            username: None,
+            refresh_minutes: 10,
+        }
+    }
+}
+
+/// 공휴일과 승인된 휴가에는 자동 출퇴근과 알림을 건너뜀
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DayOffConfig {
+    /// 한국 공휴일 (대체공휴일 포함)
+    pub public_holidays: bool,
+    /// Hiworks 에서 승인된 휴가, 반차는 반나절만 건너뜀
+    pub approved_leave: bool,
+}
+
+impl Default for DayOffConfig {
+    fn default() -> Self {
+        Self {
+            public_holidays: true,
+            approved_leave: true,
+        }
+    }
+}
+
+/// 퇴근을 잊었을 때 반복해서 보내는 안내
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CheckOutReminderConfig {
+    pub enabled: bool,
+    /// 이 시각이 지나도 퇴근 기록이 없으면 안내 (`"19:00"`)
+    pub at: Option<NaiveTime>,
+    /// 출근 기록 후 이 시간이 지나면 안내, `at` 과 둘 다 있으면 늦은 쪽
+    pub hours_after_check_in: Option<u32>,
+    /// 첫 안내 뒤 다시 안내하는 간격 (분), 안내할 때마다 두 배로 늘어남
+    pub repeat_minutes: u64,
+    /// 다시 안내하는 간격의 상한 (분)
+    pub max_repeat_minutes: u64,
+}
+
+impl Default for CheckOutReminderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            at: None,
+            hours_after_check_in: Some(9),
+            repeat_minutes: 15,
+            max_repeat_minutes: 60,
+        }
+    }
+}
+
+/// 출근 기록 없이 컴퓨터를 쓰기 시작하거나 코어타임이 다가오면 보내는 안내
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CheckInReminderConfig {
+    pub enabled: bool,
+    /// 안내하는 요일
+    pub days: Vec<Weekday>,
+    /// 이 시각 전의 사용 (새벽 작업 등) 은 출근으로 보지 않음
+    pub not_before: NaiveTime,
+    /// 코어타임 시작 시각, 다가오는데 출근 기록이 없으면 경고
+    pub core_start: Option<NaiveTime>,
+    /// 코어타임 시작 몇 분 전부터 경고할지
+    pub warn_minutes_before: u64,
+}
+
+impl Default for CheckInReminderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            not_before: NaiveTime::from_hms_opt(6, 0, 0).unwrap_or_default(),
+            core_start: None,
+            warn_minutes_before: 10,
+        }
+    }
+}
+
+/// 요청이 실패하면 Hiworks 화면과 HTML 을 저장 (선택 변경 확인용)
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CaptureConfig {
+    pub enabled: bool,
+    /// 남겨두는 최근 실패 수, 넘으면 오래된 것부터 지움
+    pub keep: u32,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            keep: 20,
+        }
+    }
+}
+
+/// CIDR 표기 IP 대역
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Subnet {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl Subnet {
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        fn masked(bits: u128, prefix: u8, width: u8) -> u128 {
+            let shift = u32::from(width - prefix);
+            bits.checked_shr(shift).unwrap_or(0)
+        }
+
+        match (self.network, addr) {
+            (IpAddr::V4(net), IpAddr::V4(addr)) => {
+                masked(u32::from(net).into(), self.prefix, 32)
+                    == masked(u32::from(addr).into(), self.prefix, 32)
+            }
+            (IpAddr::V6(net), IpAddr::V6(addr)) => {
+                masked(net.into(), self.prefix, 128) == masked(addr.into(), self.prefix, 128)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl std::str::FromStr for Subnet {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ConfigError::InvalidSubnet(s.to_string());
+        let (network, prefix) = s.trim().split_once('/').ok_or_else(invalid)?;
+        let network: IpAddr = network.parse().map_err(|_| invalid())?;
+        let prefix: u8 = prefix.parse().map_err(|_| invalid())?;
+        let width = if network.is_ipv4() { 32 } else { 128 };
+        if prefix > width {
+            return Err(invalid());
+        }
+        Ok(Self { network, prefix })
+    }
+}
+
+impl Config {
+    pub fn has_company_url(&self) -> bool {
+        self.company_url.as_deref().is_some_and(|u| !u.is_empty())
+    }
+
+    pub(crate) fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(url) = &self.company_url {
+            validate_company_url(url)?;
+        }
+        for subnet in &self.network.office_subnets {
+            subnet.parse::<Subnet>()?;
+        }
+        if let Some(source) = &self.calendar.source {
+            if crate::calendar::is_url(source) {
+                crate::calendar::parse_source(source, self.calendar.username.as_deref())
+                    .map_err(ConfigError::InvalidUrl)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 앱 데이터 디렉토리 (`~/.hiworks-commute`)
+pub fn config_dir() -> PathBuf {
+    dirs::home_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join(".hiworks-commute")
+}
+
+pub fn config_path() -> PathBuf {
+    config_dir().join("config.json")
+}
+
+/// 회사 로그인 URL 을 검사하고 정규화된 문자열을 반환합니다.
+/// `https://login.office.hiworks.com/<회사 도메인>` 형태만 허용합니다.
+pub fn validate_company_url(input: &str) -> Result<String, ConfigError> {
+    let invalid = |reason: &str| ConfigError::InvalidUrl(format!("{} ({})", input, reason));
+
+    let url = url::Url::parse(input.trim()).map_err(|_| invalid("URL 형식이 아닙니다"))?;
+
+    if url.scheme() != "https" {
+        return Err(invalid("https 주소여야 합니다"));
+    }
+    if url.host_str() != Some(LOGIN_HOST) {
+        return Err(invalid("login.office.hiworks.com 주소여야 합니다"));
+    }
+    if url.path().trim_matches('/').is_empty() {
+        return Err(invalid("회사 도메인이 없습니다"));
+    }
+
+    Ok(url.to_string())
+}
+
+/// 임시 파일에 쓴 뒤 rename 해서 중간에 끊겨도 파일이 깨지지 않게 합니다.
+pub fn write_atomic(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    std::fs::write(&tmp, contents)?;
+    std::fs::rename(&tmp, path)
+}
+
+/// 설정 파일을 읽고 쓰는 저장소. 읽기는 메모리 사본을 돌려주므로 즉시 끝납니다.
+pub struct ConfigStore {
+    path: PathBuf,
+    config: RwLock<Config>,
+}
+
+impl ConfigStore {
+    pub fn load(path: PathBuf) -> Result<Self, ConfigError> {
+        if !path.exists() {
+            return Ok(Self::empty(path));
+        }
+
+        let mut config: Config = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+        if config.version > CONFIG_VERSION {
+            return Err(ConfigError::UnsupportedVersion(config.version));
+        }
+        if config.version < CONFIG_VERSION {
+            config.version = CONFIG_VERSION;
+            write_atomic(&path, &serde_json::to_string_pretty(&config)?)?;
+        }
+
+        Ok(Self {
+            path,
+            config: RwLock::new(config),
+        })
+    }
+
+    /// 기본 설정으로 시작합니다. 첫 저장 시 `path` 에 파일이 만들어집니다.
+    pub fn empty(path: PathBuf) -> Self {
+        Self {
+            path,
+            config: RwLock::new(Config {
+                version: CONFIG_VERSION,
+                ..Config::default()
+            }),
+        }
+    }
+
+    pub fn get(&self) -> Config {
+        self.config
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// 설정을 변경하고 검증이 통과하면 파일에 저장합니다.
+    pub fn update(&self, f: impl FnOnce(&mut Config)) -> Result<Config, ConfigError> {
+        let mut guard = self.config.write().unwrap_or_else(|e| e.into_inner());
+        let mut next = guard.clone();
+        f(&mut next);
+        next.validate()?;
+
+        write_atomic(&self.path, &serde_json::to_string_pretty(&next)?)?;
+        *guard = next.clone();
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 테스트마다 따로 쓰는 임시 디렉토리
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("hiworks-config-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn company_url_must_be_hiworks_login_with_domain() {
+        assert_eq!(
+            validate_company_url(" https://login.office.hiworks.com/acme.co.kr ").unwrap(),
+            "https://login.office.hiworks.com/acme.co.kr"
+        );
+        for input in [
+            "http://login.office.hiworks.com/acme.co.kr",
+            "https://evil.example.com/acme.co.kr",
+            "https://login.office.hiworks.com.evil.com/acme",
+            "https://login.office.hiworks.com/",
+            "https://login.office.hiworks.com",
+            "login.office.hiworks.com/acme",
+        ] {
+            assert!(
+                matches!(validate_company_url(input), Err(ConfigError::InvalidUrl(_))),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn old_config_is_upgraded_and_saved() {
+        let dir = temp_dir("upgrade");
+        let path = dir.join("config.json");
+        // 버전이 없던 이전 워커의 설정 파일
+        std::fs::write(
+            &path,
+            r#"{"companyUrl":"https://login.office.hiworks.com/acme","username":"kim"}"#,
+        )
+        .unwrap();
+
+        let config = ConfigStore::load(path.clone()).unwrap().get();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.username.as_deref(), Some("kim"));
+
+        let saved: Config = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved, config);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn newer_config_version_is_rejected() {
+        let dir = temp_dir("newer");
+        let path = dir.join("config.json");
+        let contents = format!(r#"{{"version":{}}}"#, CONFIG_VERSION + 1);
+        std::fs::write(&path, &contents).unwrap();
+
+        assert!(matches!(
+            ConfigStore::load(path.clone()),
+            Err(ConfigError::UnsupportedVersion(v)) if v == CONFIG_VERSION + 1
+        ));
+        // 읽지 못한 파일은 건드리지 않음
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), contents);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn write_atomic_replaces_without_leftovers() {
+        let dir = temp_dir("atomic");
+        let path = dir.join("nested").join("config.json");
+
+        write_atomic(&path, "first").unwrap();
+        write_atomic(&path, "second").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "second");
+        let names: Vec<String> = std::fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["config.json"]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn invalid_update_is_not_applied() {
+        let dir = temp_dir("update");
+        let path = dir.join("config.json");
+        let store = ConfigStore::empty(path.clone());
+        store
+            .update(|c| c.company_url = Some("https://login.office.hiworks.com/acme".into()))
+            .unwrap();
+
+        assert!(matches!(
+            store.update(|c| c.company_url = Some("https://example.com/acme".into())),
+            Err(ConfigError::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            store.update(|c| c.network.office_subnets = vec!["10.0.0.0/33".into()]),
+            Err(ConfigError::InvalidSubnet(_))
+        ));
+
+        // 메모리와 파일 모두 이전 값 유지
+        let expected = Some("https://login.office.hiworks.com/acme".to_string());
+        assert_eq!(store.get().company_url, expected);
+        let saved: Config = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.company_url, expected);
+        assert!(saved.network.office_subnets.is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn subnet_edges() {
+        let any: Subnet = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains("203.0.113.9".parse().unwrap()));
+        assert!(!any.contains("::1".parse().unwrap()));
+
+        let host: Subnet = "10.0.0.1/32".parse().unwrap();
+        assert!(host.contains("10.0.0.1".parse().unwrap()));
+        assert!(!host.contains("10.0.0.2".parse().unwrap()));
+
+        let v6: Subnet = "fd00:1::/32".parse().unwrap();
+        assert!(v6.contains("fd00:1:ffff::7".parse().unwrap()));
+
+        assert!("10.0.0.0/33".parse::<Subnet>().is_err());
+        assert!("10.0.0.0".parse::<Subnet>().is_err());
+    }
+}
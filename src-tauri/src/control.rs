@@ -0,0 +1,507 @@
+use crate::config::{self, ControlConfig};
+use crate::protocol::WorkerRequest;
+use crate::{
+    run_worker_action, AppState, WorkerAction, CHECK_IN, CHECK_OUT, GO_OUT, SET_MEETING,
+    SET_OUTWORK, SET_WORK,
+};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde_json::json;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// 요청 줄과 헤더의 최대 크기
+const MAX_HEAD: usize = 8 * 1024;
+
+/// 요청을 다 보내지 않는 연결을 끊기까지 기다리는 시간
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 명령줄에서 보낸 요청의 응답을 기다리는 시간 (워커 큐에 앞선 요청이 있을 수 있음)
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// `POST` 로 실행하는 액션과 경로
+const ACTIONS: [(&str, &WorkerAction); 6] = [
+    ("/check-in", &CHECK_IN),
+    ("/check-out", &CHECK_OUT),
+    ("/set-work", &SET_WORK),
+    ("/go-out", &GO_OUT),
+    ("/set-meeting", &SET_MEETING),
+    ("/set-outwork", &SET_OUTWORK),
+];
+
+/// HTTP 요청에 필요한 토큰 파일 (`Authorization: Bearer <토큰>`)
+pub fn token_path() -> PathBuf {
+    config::config_dir().join("control-token")
+}
+
+/// 본인만 들어갈 수 있는 `control/` 디렉토리 안의 소켓
+pub fn socket_path() -> PathBuf {
+    config::config_dir().join("control").join("control.sock")
+}
+
+/// 토큰 파일을 읽고, 없으면 새로 만들어 본인만 읽을 수 있게 저장합니다.
+pub fn load_or_create_token() -> std::io::Result<String> {
+    let path = token_path();
+    match std::fs::read_to_string(&path) {
+        Ok(token) if !token.trim().is_empty() => return Ok(token.trim().to_string()),
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+
+    let token: String = rand::thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(32)
+        .map(char::from)
+        .collect();
+    write_private(&path, &token)?;
+    Ok(token)
+}
+
+/// 처음부터 0600 으로 만든 임시 파일에 쓰고 바꿔치기합니다.
+/// 만든 뒤 권한을 바꾸면 그 사이에 다른 사용자가 읽을 수 있습니다.
+fn write_private(path: &Path, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = PathBuf::from(tmp);
+    // 남아 있던 임시 파일은 권한이 넓을 수 있으므로 새로 만듦
+    let _ = std::fs::remove_file(&tmp);
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(&tmp)?.write_all(contents.as_bytes())?;
+    std::fs::rename(&tmp, path)
+}
+
+/// 본인만 들어갈 수 있는 디렉토리를 만듭니다. 이미 있으면 권한을 0700 으로 줄입니다.
+#[cfg(unix)]
+fn private_dir(dir: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    std::fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(dir)?;
+    std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+}
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    headers: Vec<(String, String)>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// 요청 줄과 헤더를 읽습니다. 본문은 쓰지 않으므로 무시합니다.
+fn parse_request(head: &str) -> Option<Request> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let headers = lines
+        .take_while(|l| !l.is_empty())
+        .filter_map(|l| {
+            let (k, v) = l.split_once(':')?;
+            Some((k.trim().to_string(), v.trim().to_string()))
+        })
+        .collect();
+
+    Some(Request {
+        method,
+        path,
+        headers,
+    })
+}
+
+enum Route {
+    Action(&'static WorkerAction),
+    Status,
+    MethodNotAllowed,
+    NotFound,
+}
+
+/// 트레이 메뉴와 같은 액션을 `POST /check-in` 처럼 노출합니다.
+fn route(method: &str, path: &str) -> Route {
+    let path = path.trim_end_matches('/');
+    let action = ACTIONS
+        .iter()
+        .find(|(p, _)| *p == path)
+        .map(|(_, action)| *action);
+
+    match (method, path, action) {
+        ("POST", _, Some(action)) => Route::Action(action),
+        ("GET", "/status", _) => Route::Status,
+        (_, "/status", _) | (_, _, Some(_)) => Route::MethodNotAllowed,
+        _ => Route::NotFound,
+    }
+}
+
+/// 워커 요청을 실행하는 경로, 제어 서버로 보낼 수 없는 요청이면 `None`
+pub fn action_path(request: WorkerRequest) -> Option<&'static str> {
+    ACTIONS
+        .iter()
+        .find(|(_, action)| action.request == request)
+        .map(|(path, _)| *path)
+}
+
+/// 시간 차이로 토큰을 추측할 수 없도록 끝까지 비교합니다.
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
+
+fn authorized(request: &Request, token: Option<&str>) -> bool {
+    let Some(token) = token else {
+        return true;
+    };
+    request
+        .header("Authorization")
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .is_some_and(|given| token_matches(given.trim(), token))
+}
+
+async fn read_head<S: AsyncRead + Unpin>(stream: &mut S) -> Option<String> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+        if buf.len() > MAX_HEAD {
+            return None;
+        }
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+    Some(String::from_utf8_lossy(&buf).into_owned())
+}
+
+async fn respond<S: AsyncWrite + Unpin>(stream: &mut S, status: u16, body: serde_json::Value) {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// 연결 하나에서 요청 하나를 처리합니다. `token` 이 없으면 인증을 건너뜁니다.
+async fn serve<S>(app: AppHandle, mut stream: S, token: Option<Arc<String>>)
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let request = match tokio::time::timeout(READ_TIMEOUT, read_head(&mut stream)).await {
+        Ok(Some(head)) => parse_request(&head),
+        _ => None,
+    };
+    let Some(request) = request else {
+        respond(&mut stream, 400, json!({ "ok": false, "error": "잘못된 요청" })).await;
+        return;
+    };
+
+    if !authorized(&request, token.as_deref().map(String::as_str)) {
+        respond(&mut stream, 401, json!({ "ok": false, "error": "토큰이 필요합니다" })).await;
+        return;
+    }
+
+    // 워커 큐를 거치므로 트레이/예약 실행과 겹치지 않고 차례로 처리됨
+    let (status, body) = match route(&request.method, &request.path) {
+        Route::Action(action) => match run_worker_action(&app, action).await {
+            Ok(message) => (200, json!({ "ok": true, "message": message })),
+            Err(error) => (500, json!({ "ok": false, "error": error })),
+        },
+        Route::Status => match app.state::<AppState>().worker.status().await {
+            Ok(status) => (200, json!({ "ok": true, "status": status })),
+            Err(e) => (500, json!({ "ok": false, "error": e.to_string() })),
+        },
+        Route::MethodNotAllowed => (405, json!({ "ok": false, "error": "지원하지 않는 메서드" })),
+        Route::NotFound => (404, json!({ "ok": false, "error": "알 수 없는 경로" })),
+    };
+    respond(&mut stream, status, body).await;
+}
+
+async fn listen_tcp(app: AppHandle, port: u16, token: Arc<String>) {
+    let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            tracing::warn!(error = %e, "127.0.0.1:{} 에서 열 수 없습니다", port);
+            return;
+        }
+    };
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tauri::async_runtime::spawn(serve(app.clone(), stream, Some(token.clone())));
+            }
+            Err(e) => tracing::warn!(error = %e, "연결 수락 실패"),
+        }
+    }
+}
+
+/// 0700 디렉토리 안에 소켓을 만들어 접근을 막으므로 토큰 없이 받습니다.
+/// 소켓을 만든 뒤 권한을 바꾸는 방식은 그 사이에 다른 사용자가 접속할 수 있습니다.
+#[cfg(unix)]
+async fn listen_unix(app: AppHandle) {
+    let path = socket_path();
+    if let Err(e) = private_dir(path.parent().unwrap()) {
+        tracing::warn!(error = %e, "소켓 디렉토리를 만들 수 없습니다");
+        return;
+    }
+    // 이전 실행에서 남은 소켓 파일
+    let _ = std::fs::remove_file(&path);
+
+    let listener = match tokio::net::UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            tracing::warn!(error = %e, "{} 에서 열 수 없습니다", path.display());
+            return;
+        }
+    };
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tauri::async_runtime::spawn(serve(app.clone(), stream, None));
+            }
+            Err(e) => tracing::warn!(error = %e, "연결 수락 실패"),
+        }
+    }
+}
+
+/// 요청 하나를 보내고 응답의 상태 코드와 JSON 본문을 돌려줍니다.
+fn exchange<S: Read + Write>(
+    mut stream: S,
+    method: &str,
+    path: &str,
+    token: Option<&str>,
+) -> std::io::Result<(u16, serde_json::Value)> {
+    let auth = token
+        .map(|t| format!("Authorization: Bearer {}\r\n", t))
+        .unwrap_or_default();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\n{}Content-Length: 0\r\nConnection: close\r\n\r\n",
+        method, path, auth
+    )?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    parse_response(&response).ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "제어 서버 응답을 읽을 수 없습니다",
+        )
+    })
+}
+
+fn parse_response(response: &str) -> Option<(u16, serde_json::Value)> {
+    let (head, body) = response.split_once("\r\n\r\n")?;
+    let status = head.split_whitespace().nth(1)?.parse().ok()?;
+    Some((status, serde_json::from_str(body).ok()?))
+}
+
+/// 실행 중인 트레이 앱의 제어 서버에 요청을 보냅니다 (명령줄용).
+/// Unix 소켓이 열려 있으면 그쪽으로, 아니면 토큰을 붙여 HTTP 로 보냅니다.
+pub fn call(
+    control: &ControlConfig,
+    method: &str,
+    path: &str,
+) -> std::io::Result<(u16, serde_json::Value)> {
+    #[cfg(unix)]
+    if control.unix_socket {
+        if let Ok(stream) = std::os::unix::net::UnixStream::connect(socket_path()) {
+            stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+            return exchange(stream, method, path, None);
+        }
+    }
+
+    let token = std::fs::read_to_string(token_path())?;
+    let stream = std::net::TcpStream::connect(("127.0.0.1", control.port))?;
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    exchange(stream, method, path, Some(token.trim()))
+}
+
+/// 설정에서 켜져 있으면 제어 서버를 시작합니다.
+pub fn spawn(app: AppHandle) {
+    let control = app.state::<AppState>().config.get().control;
+    if !control.enabled {
+        return;
+    }
+
+    let token = match load_or_create_token() {
+        Ok(token) => Arc::new(token),
+        Err(e) => {
+            tracing::error!(error = %e, "토큰을 만들 수 없어 제어 서버를 끕니다");
+            return;
+        }
+    };
+
+    tauri::async_runtime::spawn(listen_tcp(app.clone(), control.port, token));
+
+    #[cfg(unix)]
+    if control.unix_socket {
+        tauri::async_runtime::spawn(listen_unix(app));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_request_head() {
+        let request = parse_request(
+            "POST /check-in?source=deck HTTP/1.1\r\nHost: localhost\r\nauthorization: Bearer abc\r\n\r\n",
+        )
+        .unwrap();
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/check-in");
+        assert_eq!(request.header("Authorization"), Some("Bearer abc"));
+        assert!(parse_request("").is_none());
+    }
+
+    #[test]
+    fn routes_actions_and_status() {
+        let request = |method, path| match route(method, path) {
+            Route::Action(action) => Some(action.request),
+            _ => None,
+        };
+        assert_eq!(request("POST", "/check-in"), Some(CHECK_IN.request));
+        assert_eq!(request("POST", "/set-meeting/"), Some(SET_MEETING.request));
+        assert!(matches!(route("GET", "/status"), Route::Status));
+        assert!(matches!(route("GET", "/check-in"), Route::MethodNotAllowed));
+        assert!(matches!(route("POST", "/status"), Route::MethodNotAllowed));
+        assert!(matches!(route("GET", "/"), Route::NotFound));
+    }
+
+    #[test]
+    fn action_paths_match_routes() {
+        for (path, action) in ACTIONS {
+            assert_eq!(action_path(action.request), Some(path));
+            assert!(matches!(route("POST", path), Route::Action(a) if a.request == action.request));
+        }
+        assert_eq!(action_path(WorkerRequest::GetStatus), None);
+    }
+
+    #[test]
+    fn client_sends_token_and_reads_reply() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut head = Vec::new();
+            let mut byte = [0u8; 1];
+            while !head.ends_with(b"\r\n\r\n") {
+                stream.read_exact(&mut byte).unwrap();
+                head.push(byte[0]);
+            }
+            let body = r#"{"ok":false,"error":"먼저 설정에서 회사 URL을 입력해주세요"}"#;
+            write!(
+                stream,
+                "HTTP/1.1 500 Internal Server Error\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            parse_request(&String::from_utf8(head).unwrap()).unwrap()
+        });
+
+        let stream = std::net::TcpStream::connect(("127.0.0.1", port)).unwrap();
+        let (status, body) = exchange(stream, "POST", "/check-in", Some("secret")).unwrap();
+        assert_eq!(status, 500);
+        assert_eq!(body["error"], "먼저 설정에서 회사 URL을 입력해주세요");
+
+        let request = server.join().unwrap();
+        assert_eq!(request.path, "/check-in");
+        assert!(authorized(&request, Some("secret")));
+    }
+
+    #[test]
+    fn requires_matching_token() {
+        let request = |auth: &str| Request {
+            method: "GET".to_string(),
+            path: "/status".to_string(),
+            headers: vec![("Authorization".to_string(), auth.to_string())],
+        };
+
+        assert!(authorized(&request("Bearer secret"), Some("secret")));
+        assert!(!authorized(&request("Bearer secreT"), Some("secret")));
+        assert!(!authorized(&request("Bearer secret2"), Some("secret")));
+        assert!(!authorized(&request("secret"), Some("secret")));
+        // Unix 소켓
+        assert!(authorized(&request(""), None));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn token_and_socket_dir_are_private_from_the_start() {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = |p: &Path| std::fs::metadata(p).unwrap().permissions().mode() & 0o777;
+
+        let dir = std::env::temp_dir().join(format!("hiworks-control-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let token = dir.join("control-token");
+        // 이전에 권한이 넓게 남은 임시 파일
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("control-token.tmp"), "old").unwrap();
+        std::fs::set_permissions(
+            dir.join("control-token.tmp"),
+            std::fs::Permissions::from_mode(0o644),
+        )
+        .unwrap();
+
+        write_private(&token, "secret").unwrap();
+        assert_eq!(std::fs::read_to_string(&token).unwrap(), "secret");
+        assert_eq!(mode(&token), 0o600);
+        assert!(!dir.join("control-token.tmp").exists());
+
+        let socket_dir = dir.join("control");
+        std::fs::create_dir(&socket_dir).unwrap();
+        std::fs::set_permissions(&socket_dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+        private_dir(&socket_dir).unwrap();
+        assert_eq!(mode(&socket_dir), 0o700);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
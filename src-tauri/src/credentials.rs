@@ -0,0 +1,201 @@
+use crate::config;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// 키체인에 저장할 때 사용하는 서비스 이름
+const KEYRING_SERVICE: &str = "com.hiworks.commute";
+
+/// 계정 구분 없이 하나의 비밀번호만 저장할 때 사용하는 키
+pub const DEFAULT_ACCOUNT: &str = "default";
+
+/// CalDAV 서버 비밀번호를 저장하는 키
+pub const CALENDAR_ACCOUNT: &str = "calendar";
+
+#[derive(Error, Debug)]
+pub enum CredentialError {
+    #[error("키체인 오류: {0}")]
+    Keyring(#[from] keyring::Error),
+    #[error("설정 파일 오류: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("설정 파일 파싱 오류: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// 비밀번호 저장소. 실제 앱은 OS 키체인을, 테스트는 메모리 저장소를 사용합니다.
+pub trait CredentialStore: Send + Sync {
+    fn get(&self, account: &str) -> Result<Option<String>, CredentialError>;
+    fn set(&self, account: &str, password: &str) -> Result<(), CredentialError>;
+    fn delete(&self, account: &str) -> Result<(), CredentialError>;
+}
+
+/// macOS Keychain / Secret Service / Windows Credential Manager
+pub struct KeyringStore;
+
+impl CredentialStore for KeyringStore {
+    fn get(&self, account: &str) -> Result<Option<String>, CredentialError> {
+        match keyring::Entry::new(KEYRING_SERVICE, account)?.get_password() {
+            Ok(password) => Ok(Some(password)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    fn set(&self, account: &str, password: &str) -> Result<(), CredentialError> {
+        keyring::Entry::new(KEYRING_SERVICE, account)?.set_password(password)?;
+        Ok(())
+    }
+
+    fn delete(&self, account: &str) -> Result<(), CredentialError> {
+        match keyring::Entry::new(KEYRING_SERVICE, account)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// 프로세스 메모리에만 보관하는 저장소 (테스트용)
+#[derive(Default)]
+pub struct MemoryStore {
+    passwords: Mutex<HashMap<String, String>>,
+}
+
+impl CredentialStore for MemoryStore {
+    fn get(&self, account: &str) -> Result<Option<String>, CredentialError> {
+        let passwords = self.passwords.lock().unwrap_or_else(|e| e.into_inner());
+        Ok(passwords.get(account).cloned())
+    }
+
+    fn set(&self, account: &str, password: &str) -> Result<(), CredentialError> {
+        let mut passwords = self.passwords.lock().unwrap_or_else(|e| e.into_inner());
+        passwords.insert(account.to_string(), password.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, account: &str) -> Result<(), CredentialError> {
+        let mut passwords = self.passwords.lock().unwrap_or_else(|e| e.into_inner());
+        passwords.remove(account);
+        Ok(())
+    }
+}
+
+/// 설정 파일 `path` 에 평문으로 남아있는 비밀번호를 저장소로 옮기고 파일에서 지웁니다.
+/// 옮긴 비밀번호가 있으면 `true` 를 반환합니다.
+pub fn migrate_plaintext_password(
+    path: &Path,
+    store: &dyn CredentialStore,
+) -> Result<bool, CredentialError> {
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    let mut raw: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    let Some(object) = raw.as_object_mut() else {
+        return Ok(false);
+    };
+
+    let password = match object.get("password") {
+        Some(serde_json::Value::String(p)) if !p.is_empty() => p.clone(),
+        Some(_) => {
+            // null 이나 빈 값은 키만 정리
+            object.remove("password");
+            config::write_atomic(path, &serde_json::to_string_pretty(&raw)?)?;
+            return Ok(false);
+        }
+        None => return Ok(false),
+    };
+
+    // 키체인 저장이 성공한 뒤에만 파일에서 지움
+    store.set(DEFAULT_ACCOUNT, &password)?;
+    object.remove("password");
+    config::write_atomic(path, &serde_json::to_string_pretty(&raw)?)?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// 키체인이 잠겨 저장에 실패하는 저장소
+    struct LockedStore;
+
+    impl CredentialStore for LockedStore {
+        fn get(&self, _account: &str) -> Result<Option<String>, CredentialError> {
+            Ok(None)
+        }
+
+        fn set(&self, _account: &str, _password: &str) -> Result<(), CredentialError> {
+            Err(CredentialError::Io(std::io::Error::other(
+                "키체인이 잠겨 있습니다",
+            )))
+        }
+
+        fn delete(&self, _account: &str) -> Result<(), CredentialError> {
+            Ok(())
+        }
+    }
+
+    fn plaintext_config(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "hiworks-credentials-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        std::fs::write(
+            &path,
+            r#"{"version":1,"username":"kim","password":"hunter2"}"#,
+        )
+        .unwrap();
+        path
+    }
+
+    fn read(path: &Path) -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn migration_moves_password_into_store() {
+        let path = plaintext_config("move");
+        let store = MemoryStore::default();
+
+        assert!(migrate_plaintext_password(&path, &store).unwrap());
+        assert_eq!(
+            store.get(DEFAULT_ACCOUNT).unwrap().as_deref(),
+            Some("hunter2")
+        );
+        assert_eq!(
+            read(&path),
+            serde_json::json!({ "version": 1, "username": "kim" })
+        );
+
+        // 두 번째 실행은 옮길 것이 없고 저장소도 그대로
+        assert!(!migrate_plaintext_password(&path, &store).unwrap());
+        assert_eq!(
+            store.get(DEFAULT_ACCOUNT).unwrap().as_deref(),
+            Some("hunter2")
+        );
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn store_failure_keeps_file() {
+        let path = plaintext_config("locked");
+        let before = std::fs::read_to_string(&path).unwrap();
+
+        assert!(migrate_plaintext_password(&path, &LockedStore).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), before);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn missing_config_is_nothing_to_migrate() {
+        let path = std::env::temp_dir().join("hiworks-credentials-missing/config.json");
+        assert!(!migrate_plaintext_password(&path, &MemoryStore::default()).unwrap());
+    }
+}
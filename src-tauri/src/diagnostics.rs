@@ -0,0 +1,542 @@
+use crate::config::ConfigStore;
+use crate::credentials::{CredentialStore, DEFAULT_ACCOUNT};
+use crate::logging;
+use crate::playwright::{self, PlaywrightError, PlaywrightWorker, WorkerLaunch};
+use crate::profile::{Profile, Profiles};
+use crate::protocol::AttendanceStatus;
+use crate::AppState;
+use chrono::{DateTime, Datelike, Local, Timelike};
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+/// 워커가 이보다 늦게 준비되면 경고
+const SLOW_STARTUP: Duration = Duration::from_secs(10);
+
+/// 설정 파일에서 가리는 키 (부분 일치)
+const PRIVATE_KEYS: [&str; 4] = ["password", "token", "username", "email"];
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// 진단 항목 하나의 결과
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &str, status: CheckStatus, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            status,
+            detail: detail.into(),
+        }
+    }
+}
+
+/// 지원 요청에 첨부하는 진단 결과
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Report {
+    pub created_at: DateTime<Local>,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub profile: String,
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    pub fn new(profile: &Profile, checks: Vec<Check>) -> Self {
+        Self {
+            created_at: Local::now(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            profile: profile.id.clone(),
+            checks,
+        }
+    }
+
+    /// 경고/실패 항목
+    pub fn problems(&self) -> Vec<&Check> {
+        self.checks
+            .iter()
+            .filter(|c| c.status != CheckStatus::Ok)
+            .collect()
+    }
+
+    /// 알림에 보여줄 한두 줄 요약
+    pub fn summary(&self) -> String {
+        let problems = self.problems();
+        if problems.is_empty() {
+            return format!("{}개 항목 모두 정상입니다", self.checks.len());
+        }
+        let names: Vec<&str> = problems.iter().map(|c| c.name.as_str()).collect();
+        format!(
+            "확인이 필요한 항목 {}개: {}",
+            problems.len(),
+            names.join(", ")
+        )
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "Hiworks 출퇴근 {} ({}/{}), 프로필 {}\n{}\n\n",
+            self.app_version,
+            self.os,
+            self.arch,
+            self.profile,
+            self.created_at.format("%Y-%m-%d %H:%M:%S")
+        );
+        for check in &self.checks {
+            let mark = match check.status {
+                CheckStatus::Ok => "정상",
+                CheckStatus::Warn => "경고",
+                CheckStatus::Fail => "실패",
+            };
+            text.push_str(&format!("[{}] {}: {}\n", mark, check.name, check.detail));
+        }
+        text
+    }
+}
+
+fn node_version(node: &Path) -> Option<String> {
+    let output = Command::new(node).arg("--version").output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// 번들, 환경 변수, 사용자 캐시 순서로 Playwright 브라우저 폴더를 찾습니다.
+fn browsers_dir(bundle: Option<&Path>) -> Option<PathBuf> {
+    let bundled = bundle.map(|b| b.join("browsers"));
+    let from_env = std::env::var_os("PLAYWRIGHT_BROWSERS_PATH").map(PathBuf::from);
+    let cache = dirs::cache_dir().map(|c| c.join("ms-playwright"));
+    [bundled, from_env, cache]
+        .into_iter()
+        .flatten()
+        .find(|dir| dir.exists())
+}
+
+fn has_chromium(dir: &Path) -> bool {
+    std::fs::read_dir(dir).is_ok_and(|entries| {
+        entries
+            .flatten()
+            .any(|e| e.file_name().to_string_lossy().starts_with("chromium"))
+    })
+}
+
+/// 워커를 띄우지 않고 확인할 수 있는 설치/설정 항목
+pub fn environment_checks(profile: &Profile, credentials: &dyn CredentialStore) -> Vec<Check> {
+    let mut checks = Vec::new();
+
+    let bundle = playwright::find_bundle_dir();
+    checks.push(match &bundle {
+        Some(dir) => Check::new("번들", CheckStatus::Ok, dir.display().to_string()),
+        None => Check::new(
+            "번들",
+            CheckStatus::Warn,
+            "번들 폴더가 없어 시스템 node 와 개발용 스크립트를 찾습니다",
+        ),
+    });
+
+    checks.push(match playwright::find_node() {
+        Some(node) => match node_version(&node) {
+            Some(version) => Check::new(
+                "Node.js",
+                CheckStatus::Ok,
+                format!("{} ({})", version, node.display()),
+            ),
+            None => Check::new(
+                "Node.js",
+                CheckStatus::Fail,
+                format!("{} 를 실행할 수 없습니다", node.display()),
+            ),
+        },
+        None => Check::new(
+            "Node.js",
+            CheckStatus::Fail,
+            PlaywrightError::NodeNotFound.to_string(),
+        ),
+    });
+
+    checks.push(match playwright::find_script_and_workdir() {
+        Some((script, _)) => Check::new(
+            "워커 스크립트",
+            CheckStatus::Ok,
+            script.display().to_string(),
+        ),
+        None => Check::new(
+            "워커 스크립트",
+            CheckStatus::Fail,
+            PlaywrightError::ScriptNotFound.to_string(),
+        ),
+    });
+
+    checks.push(match browsers_dir(bundle.as_deref()) {
+        Some(dir) if has_chromium(&dir) => {
+            Check::new("브라우저", CheckStatus::Ok, dir.display().to_string())
+        }
+        Some(dir) => Check::new(
+            "브라우저",
+            CheckStatus::Fail,
+            format!("{} 에 Chromium 이 없습니다", dir.display()),
+        ),
+        None => Check::new(
+            "브라우저",
+            CheckStatus::Fail,
+            "Playwright 브라우저가 설치되어 있지 않습니다",
+        ),
+    });
+
+    checks.push(match ConfigStore::load(profile.config_path()) {
+        Ok(store) => {
+            let config = store.get();
+            match config.validate() {
+                Err(e) => Check::new("설정", CheckStatus::Fail, e.to_string()),
+                Ok(()) if !config.has_company_url() => {
+                    Check::new("설정", CheckStatus::Warn, "회사 URL 이 설정되지 않았습니다")
+                }
+                Ok(()) => Check::new(
+                    "설정",
+                    CheckStatus::Ok,
+                    profile.config_path().display().to_string(),
+                ),
+            }
+        }
+        Err(e) => Check::new("설정", CheckStatus::Fail, e.to_string()),
+    });
+
+    checks.push(match credentials.get(DEFAULT_ACCOUNT) {
+        Ok(Some(_)) => Check::new("비밀번호", CheckStatus::Ok, "키체인에 저장되어 있습니다"),
+        Ok(None) => Check::new(
+            "비밀번호",
+            CheckStatus::Warn,
+            "저장된 비밀번호가 없어 자동 로그인할 수 없습니다",
+        ),
+        Err(e) => Check::new("비밀번호", CheckStatus::Fail, e.to_string()),
+    });
+
+    checks
+}
+
+/// 프로필을 고르지 못했으면 실패로 남기고, 나머지는 기본 프로필로 확인합니다.
+pub fn profile_check<E: fmt::Display>(profile: Result<Profile, E>) -> (Profile, Check) {
+    match profile {
+        Ok(profile) => {
+            let check = Check::new("프로필", CheckStatus::Ok, profile.id.clone());
+            (profile, check)
+        }
+        Err(e) => (
+            Profiles::default().active().clone(),
+            Check::new(
+                "프로필",
+                CheckStatus::Fail,
+                format!("{} (기본 프로필로 확인합니다)", e),
+            ),
+        ),
+    }
+}
+
+/// 새 워커를 띄워 `ready` 까지 걸린 시간을 잽니다. 브라우저는 열지 않습니다.
+pub fn startup_check() -> Check {
+    let mut worker = match WorkerLaunch::discover() {
+        Ok(launch) => PlaywrightWorker::with_launch(launch),
+        Err(e) => return Check::new("워커 시작", CheckStatus::Fail, e.to_string()),
+    };
+
+    let started = Instant::now();
+    let result = worker.start();
+    let elapsed = started.elapsed();
+    let _ = worker.stop();
+
+    match result {
+        Ok(()) if elapsed > SLOW_STARTUP => Check::new(
+            "워커 시작",
+            CheckStatus::Warn,
+            format!("{:.1}초 (느림)", elapsed.as_secs_f64()),
+        ),
+        Ok(()) => Check::new(
+            "워커 시작",
+            CheckStatus::Ok,
+            format!("{:.1}초", elapsed.as_secs_f64()),
+        ),
+        Err(e) => Check::new("워커 시작", CheckStatus::Fail, e.to_string()),
+    }
+}
+
+/// 열린 세션이 없으면 `getStatus` 로 자동 로그인까지 해 본 결과
+pub fn login_check<E: fmt::Display>(
+    logged_in: Result<bool, E>,
+    status: Option<Result<AttendanceStatus, E>>,
+) -> Check {
+    match (logged_in, status) {
+        (Ok(true), _) => Check::new("로그인", CheckStatus::Ok, "Hiworks 세션이 열려 있습니다"),
+        (Ok(false), Some(Ok(_))) => {
+            Check::new("로그인", CheckStatus::Ok, "세션이 없어 자동 로그인했습니다")
+        }
+        (Ok(false), Some(Err(e))) => Check::new(
+            "로그인",
+            CheckStatus::Fail,
+            format!("세션이 만료되었고 자동 로그인에 실패했습니다: {}", e),
+        ),
+        (Ok(false), None) => Check::new("로그인", CheckStatus::Warn, "로그인되어 있지 않습니다"),
+        (Err(e), _) => Check::new("로그인", CheckStatus::Fail, e.to_string()),
+    }
+}
+
+/// 트레이 앱이 브라우저 세션을 쓰고 있어 명령줄에서는 로그인을 확인하지 않음
+pub fn app_running_check() -> Check {
+    Check::new(
+        "로그인",
+        CheckStatus::Warn,
+        "트레이 앱이 실행 중이라 확인하지 않았습니다. 트레이 메뉴의 로그 → 진단으로 확인해주세요",
+    )
+}
+
+/// 트레이 앱의 워커로 로그인까지 확인합니다.
+pub async fn run(state: &AppState) -> Report {
+    let profile = state.profile.clone();
+    let credentials = state.credentials.clone();
+    let mut checks = tauri::async_runtime::spawn_blocking(move || {
+        let mut checks = environment_checks(&profile, credentials.as_ref());
+        checks.push(startup_check());
+        checks
+    })
+    .await
+    .unwrap_or_default();
+
+    let logged_in = state.worker.is_logged_in().await;
+    let status = match logged_in {
+        Ok(false) if state.config.get().has_company_url() => Some(state.worker.status().await),
+        _ => None,
+    };
+    checks.push(login_check(logged_in, status));
+
+    Report::new(&state.profile, checks)
+}
+
+/// 비밀번호, 토큰, 아이디, 이메일 값을 가립니다.
+pub fn redact_config(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                let key = key.to_ascii_lowercase();
+                if PRIVATE_KEYS.iter().any(|k| key.contains(k)) && !value.is_null() {
+                    *value = "***".into();
+                } else {
+                    redact_config(value);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_config),
+        _ => {}
+    }
+}
+
+fn config_entry(profile: &Profile) -> String {
+    let contents = match std::fs::read_to_string(profile.config_path()) {
+        Ok(contents) => contents,
+        Err(e) => return format!("설정 파일을 읽을 수 없습니다: {}", e),
+    };
+    match serde_json::from_str::<serde_json::Value>(&contents) {
+        Ok(mut value) => {
+            redact_config(&mut value);
+            serde_json::to_string_pretty(&value).unwrap_or_default()
+        }
+        // 파싱이 안 되면 키 이름으로만 가림
+        Err(_) => logging::redact(&contents),
+    }
+}
+
+/// 진단 결과, 가린 설정, 로그를 zip 으로 묶어 `dir` 에 저장하고 경로를 돌려줍니다.
+pub fn write_bundle(report: &Report, profile: &Profile, dir: &Path) -> std::io::Result<PathBuf> {
+    let mut entries = vec![
+        ("report.txt".to_string(), report.to_text().into_bytes()),
+        (
+            "report.json".to_string(),
+            serde_json::to_vec_pretty(report).map_err(std::io::Error::other)?,
+        ),
+        (
+            "config.json".to_string(),
+            config_entry(profile).into_bytes(),
+        ),
+    ];
+    for path in logging::log_files() {
+        let text = std::fs::read_to_string(&path).unwrap_or_default();
+        let redacted: String = text.lines().map(|l| logging::redact(l) + "\n").collect();
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        entries.push((format!("logs/{}", name), redacted.into_bytes()));
+    }
+
+    std::fs::create_dir_all(dir)?;
+    let path = dir.join(format!(
+        "hiworks-diagnostics-{}.zip",
+        report.created_at.format("%Y%m%d-%H%M%S")
+    ));
+    std::fs::write(&path, zip(&entries, report.created_at))?;
+    Ok(path)
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// 압축하지 않은 (stored) zip 파일을 만듭니다. 로그 크기가 작아 압축은 하지 않습니다.
+fn zip(entries: &[(String, Vec<u8>)], modified: DateTime<Local>) -> Vec<u8> {
+    let dos_time =
+        ((modified.hour() << 11) | (modified.minute() << 5) | (modified.second() / 2)) as u16;
+    let dos_date = (((modified.year() - 1980).max(0) as u32) << 9
+        | (modified.month() << 5)
+        | modified.day()) as u16;
+    // 파일 이름이 UTF-8 임을 표시
+    const UTF8_FLAG: u16 = 0x0800;
+
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+    for (name, data) in entries {
+        let offset = out.len() as u32;
+        let crc = crc32(data);
+        let header = |out: &mut Vec<u8>| {
+            out.extend_from_slice(&UTF8_FLAG.to_le_bytes());
+            out.extend_from_slice(&0u16.to_le_bytes()); // stored
+            out.extend_from_slice(&dos_time.to_le_bytes());
+            out.extend_from_slice(&dos_date.to_le_bytes());
+            out.extend_from_slice(&crc.to_le_bytes());
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            out.extend_from_slice(&0u16.to_le_bytes()); // extra
+        };
+
+        out.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        out.extend_from_slice(&20u16.to_le_bytes());
+        header(&mut out);
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(data);
+
+        central.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        central.extend_from_slice(&20u16.to_le_bytes()); // made by
+        central.extend_from_slice(&20u16.to_le_bytes()); // needed
+        header(&mut central);
+        central.extend_from_slice(&0u16.to_le_bytes()); // comment
+        central.extend_from_slice(&0u16.to_le_bytes()); // disk
+        central.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+        central.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+
+    let central_offset = out.len() as u32;
+    out.extend_from_slice(&central);
+    out.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    out.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    out.extend_from_slice(&central_offset.to_le_bytes());
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_private_config_values() {
+        let mut config = serde_json::json!({
+            "companyUrl": "https://login.office.hiworks.com/acme",
+            "username": "kim",
+            "calendar": { "email": "kim@acme.com", "username": null, "source": "work.ics" },
+            "control": { "token": "abc" }
+        });
+        redact_config(&mut config);
+        assert_eq!(
+            config,
+            serde_json::json!({
+                "companyUrl": "https://login.office.hiworks.com/acme",
+                "username": "***",
+                "calendar": { "email": "***", "username": null, "source": "work.ics" },
+                "control": { "token": "***" }
+            })
+        );
+    }
+
+    #[test]
+    fn writes_readable_zip() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+
+        let at = Local::now();
+        let entries = vec![
+            ("report.txt".to_string(), "정상".as_bytes().to_vec()),
+            ("logs/app.log".to_string(), b"line\n".to_vec()),
+        ];
+        let bytes = zip(&entries, at);
+
+        assert_eq!(&bytes[..4], b"PK\x03\x04");
+        let eocd = &bytes[bytes.len() - 22..];
+        assert_eq!(&eocd[..4], b"PK\x05\x06");
+        assert_eq!(u16::from_le_bytes([eocd[10], eocd[11]]), 2);
+
+        let central_offset = u32::from_le_bytes([eocd[16], eocd[17], eocd[18], eocd[19]]) as usize;
+        assert_eq!(&bytes[central_offset..central_offset + 4], b"PK\x01\x02");
+
+        // 첫 항목의 이름과 내용
+        let name_len = u16::from_le_bytes([bytes[26], bytes[27]]) as usize;
+        assert_eq!(&bytes[30..30 + name_len], b"report.txt");
+        assert_eq!(&bytes[30 + name_len..30 + name_len + 6], "정상".as_bytes());
+    }
+
+    #[test]
+    fn login_check_reports_expired_session() {
+        let check = login_check(Ok(false), Some(Err(PlaywrightError::Crashed)));
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert_eq!(
+            login_check::<PlaywrightError>(Ok(true), None).status,
+            CheckStatus::Ok
+        );
+
+        let report = Report::new(
+            &Profile {
+                id: "default".to_string(),
+                name: "기본".to_string(),
+            },
+            vec![check],
+        );
+        assert_eq!(report.summary(), "확인이 필요한 항목 1개: 로그인");
+    }
+
+    #[test]
+    fn unknown_profile_is_a_failed_check() {
+        let (profile, check) = profile_check::<String>(Err("프로필 acme 가 없습니다".into()));
+        assert!(profile.is_default());
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.detail.starts_with("프로필 acme 가 없습니다"));
+    }
+}
@@ -0,0 +1,282 @@
+use crate::config;
+use crate::history::HistoryEntry;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExportFormat {
+    Csv,
+    Ics,
+}
+
+impl ExportFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Ics => "ics",
+        }
+    }
+}
+
+/// 내보낸 파일을 둘 곳 (다운로드 폴더, 없으면 앱 데이터 디렉토리)
+pub fn export_path(format: ExportFormat, from: NaiveDate, to: NaiveDate) -> PathBuf {
+    let dir = dirs::download_dir().unwrap_or_else(|| config::config_dir().join("exports"));
+    dir.join(format!(
+        "hiworks-{}-{}.{}",
+        from.format("%Y%m%d"),
+        to.format("%Y%m%d"),
+        format.extension()
+    ))
+}
+
+/// 기록의 action 을 사람이 읽는 이름으로
+fn action_label(action: &str) -> &str {
+    match action {
+        "checkIn" => "출근",
+        "checkOut" => "퇴근",
+        "setWork" => "업무",
+        "goOut" => "외출",
+        "setMeeting" => "회의",
+        "setOutwork" => "외근",
+        other => other,
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// 실패한 요청까지 모두 한 줄씩 내보냅니다.
+///
+/// 엑셀에서 한글이 깨지지 않도록 UTF-8 BOM 을 붙입니다.
+pub fn to_csv(entries: &[HistoryEntry]) -> String {
+    let mut out = String::from("\u{feff}날짜,요청 시각,구분,Hiworks 시각,결과,메시지\r\n");
+    for entry in entries {
+        let row = [
+            entry.requested_at.format("%Y-%m-%d").to_string(),
+            entry.requested_at.format("%H:%M:%S").to_string(),
+            action_label(&entry.action).to_string(),
+            entry
+                .check_time
+                .map(|t| t.format("%H:%M:%S").to_string())
+                .unwrap_or_default(),
+            if entry.success { "성공" } else { "실패" }.to_string(),
+            entry.message.clone(),
+        ];
+        let row: Vec<String> = row.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+/// 성공한 요청의 시각. 출퇴근은 Hiworks 에 표시된 시각을 우선합니다.
+fn effective_time(entry: &HistoryEntry) -> NaiveDateTime {
+    let requested = entry.requested_at.naive_local();
+    match entry.check_time {
+        Some(t) => requested.date().and_time(t),
+        None => requested,
+    }
+}
+
+fn ics_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+fn ics_time(at: NaiveDateTime) -> String {
+    at.format("%Y%m%dT%H%M%S").to_string()
+}
+
+/// 한 줄이 75 바이트를 넘지 않도록 접습니다 (RFC 5545 3.1).
+fn push_folded(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+struct Event {
+    uid: String,
+    summary: String,
+    start: NaiveDateTime,
+    end: Option<NaiveDateTime>,
+    related_to: Option<String>,
+    description: Option<String>,
+}
+
+/// 근무한 날마다 출근부터 퇴근까지 이벤트 하나를 만들고,
+/// 그날의 근무 상태 변경은 `RELATED-TO` 로 묶인 하위 이벤트로 넣습니다.
+/// 성공한 요청만 사용하며 출근 기록이 없는 날은 건너뜁니다.
+pub fn to_ics(entries: &[HistoryEntry], now: DateTime<Utc>) -> String {
+    let mut days: BTreeMap<NaiveDate, Vec<&HistoryEntry>> = BTreeMap::new();
+    for entry in entries.iter().filter(|e| e.success) {
+        days.entry(entry.requested_at.date_naive())
+            .or_default()
+            .push(entry);
+    }
+
+    let mut events = Vec::new();
+    for (date, mut day) in days {
+        day.sort_by_key(|e| effective_time(e));
+
+        let Some(check_in) = day.iter().find(|e| e.action == "checkIn") else {
+            continue;
+        };
+        let start = effective_time(check_in);
+        let end = day
+            .iter()
+            .rev()
+            .find(|e| e.action == "checkOut")
+            .map(|e| effective_time(e));
+        let day_uid = format!("{}-workday@hiworks-commute", date.format("%Y%m%d"));
+
+        events.push(Event {
+            uid: day_uid.clone(),
+            summary: "근무".to_string(),
+            start,
+            end,
+            related_to: None,
+            description: None,
+        });
+
+        // 상태는 다음 상태 변경이나 퇴근까지 유지됨
+        let changes: Vec<&HistoryEntry> = day
+            .iter()
+            .copied()
+            .filter(|e| !matches!(e.action.as_str(), "checkIn" | "checkOut"))
+            .collect();
+        for (i, change) in changes.iter().enumerate() {
+            let at = effective_time(change);
+            let until = changes.get(i + 1).map(|next| effective_time(next)).or(end);
+            events.push(Event {
+                uid: format!(
+                    "{}-{}@hiworks-commute",
+                    at.format("%Y%m%dT%H%M%S"),
+                    change.action
+                ),
+                summary: action_label(&change.action).to_string(),
+                start: at,
+                end: until.filter(|u| *u > at),
+                related_to: Some(day_uid.clone()),
+                description: Some(change.message.clone()),
+            });
+        }
+    }
+
+    let stamp = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let mut out = String::new();
+    push_folded(&mut out, "BEGIN:VCALENDAR");
+    push_folded(&mut out, "VERSION:2.0");
+    push_folded(&mut out, "PRODID:-//hiworks-commute//KO");
+    push_folded(&mut out, "CALSCALE:GREGORIAN");
+    for event in events {
+        push_folded(&mut out, "BEGIN:VEVENT");
+        push_folded(&mut out, &format!("UID:{}", event.uid));
+        push_folded(&mut out, &format!("DTSTAMP:{}", stamp));
+        push_folded(&mut out, &format!("DTSTART:{}", ics_time(event.start)));
+        if let Some(end) = event.end {
+            push_folded(&mut out, &format!("DTEND:{}", ics_time(end)));
+        }
+        push_folded(&mut out, &format!("SUMMARY:{}", ics_text(&event.summary)));
+        if let Some(description) = event.description {
+            push_folded(&mut out, &format!("DESCRIPTION:{}", ics_text(&description)));
+        }
+        if let Some(parent) = event.related_to {
+            push_folded(&mut out, &format!("RELATED-TO;RELTYPE=PARENT:{}", parent));
+        }
+        push_folded(&mut out, "END:VEVENT");
+    }
+    push_folded(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Local, NaiveTime, TimeZone};
+
+    fn entry(hour: u32, minute: u32, action: &str, success: bool) -> HistoryEntry {
+        HistoryEntry {
+            requested_at: Local.with_ymd_and_hms(2026, 10, 5, hour, minute, 0).unwrap(),
+            action: action.to_string(),
+            check_time: (success && matches!(action, "checkIn" | "checkOut"))
+                .then(|| NaiveTime::from_hms_opt(hour, minute + 1, 0).unwrap()),
+            success,
+            message: format!("{} 완료", action),
+            capture: None,
+        }
+    }
+
+    #[test]
+    fn csv_escapes_and_keeps_failures() {
+        let mut failed = entry(8, 50, "checkIn", false);
+        failed.message = "오류: \"로그인\", 실패".to_string();
+        let csv = to_csv(&[failed, entry(8, 55, "checkIn", true)]);
+
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert!(lines[0].starts_with('\u{feff}'));
+        assert_eq!(
+            lines[1],
+            "2026-10-05,08:50:00,출근,,실패,\"오류: \"\"로그인\"\", 실패\""
+        );
+        assert_eq!(lines[2], "2026-10-05,08:55:00,출근,08:56:00,성공,checkIn 완료");
+    }
+
+    #[test]
+    fn ics_has_workday_and_status_sub_events() {
+        let entries = [
+            entry(8, 55, "checkIn", true),
+            entry(10, 0, "setMeeting", true),
+            entry(11, 0, "setWork", true),
+            entry(13, 0, "goOut", false),
+            entry(18, 0, "checkOut", true),
+        ];
+        let ics = to_ics(&entries, Utc.with_ymd_and_hms(2026, 10, 6, 0, 0, 0).unwrap());
+
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 3);
+        assert!(ics.contains("DTSTART:20261005T085600\r\nDTEND:20261005T180100\r\nSUMMARY:근무"));
+        assert!(ics.contains("DTSTART:20261005T100000\r\nDTEND:20261005T110000\r\nSUMMARY:회의"));
+        assert!(ics.contains("DTSTART:20261005T110000\r\nDTEND:20261005T180100\r\nSUMMARY:업무"));
+        assert_eq!(
+            ics.matches("RELATED-TO;RELTYPE=PARENT:20261005-workday@hiworks-commute").count(),
+            2
+        );
+        assert!(!ics.contains("외출"));
+    }
+
+    #[test]
+    fn ics_skips_days_without_check_in() {
+        let ics = to_ics(
+            &[entry(10, 0, "setMeeting", true)],
+            Utc.with_ymd_and_hms(2026, 10, 6, 0, 0, 0).unwrap(),
+        );
+        assert!(!ics.contains("BEGIN:VEVENT"));
+    }
+
+    #[test]
+    fn folds_long_lines() {
+        let mut out = String::new();
+        push_folded(&mut out, &format!("DESCRIPTION:{}", "가".repeat(40)));
+        for line in out.split("\r\n") {
+            assert!(line.len() <= 75);
+        }
+        assert_eq!(out.replace("\r\n ", ""), format!("DESCRIPTION:{}\r\n", "가".repeat(40)));
+    }
+}
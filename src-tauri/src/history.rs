@@ -0,0 +1,160 @@
+use crate::journal::Journal;
+use crate::profile::Profile;
+use crate::protocol::{ActionReply, FailureCapture, WorkerRequest};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+/// `history.jsonl` 에 남기는 출퇴근/상태 변경 기록
+///
+/// Hiworks 쪽 기록이 사라져도 언제 버튼을 눌렀는지 확인할 수 있도록
+/// 성공 여부와 관계없이 모든 요청을 남깁니다.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    /// 워커에 요청을 보낸 시각
+    pub requested_at: DateTime<Local>,
+    pub action: String,
+    /// Hiworks 페이지의 `.check-time` 에 표시된 시각 (출퇴근만)
+    pub check_time: Option<NaiveTime>,
+    pub success: bool,
+    /// 성공 메시지 또는 오류 내용
+    pub message: String,
+    /// 실패 시점의 화면 캡처 (`capture.enabled` 일 때만)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub capture: Option<FailureCapture>,
+}
+
+impl HistoryEntry {
+    pub fn from_result<E: std::fmt::Display>(
+        requested_at: DateTime<Local>,
+        request: WorkerRequest,
+        result: &Result<ActionReply, E>,
+    ) -> Self {
+        Self {
+            requested_at,
+            action: request.action().to_string(),
+            check_time: result.as_ref().ok().and_then(|r| r.check_time),
+            success: result.is_ok(),
+            message: match result {
+                Ok(reply) => reply.message.clone(),
+                Err(e) => e.to_string(),
+            },
+            capture: None,
+        }
+    }
+
+    pub fn with_capture(mut self, capture: Option<&FailureCapture>) -> Self {
+        self.capture = capture.cloned();
+        self
+    }
+}
+
+/// 화면 캡처가 남은 가장 최근 실패
+pub fn last_capture(journal: &Journal) -> std::io::Result<Option<HistoryEntry>> {
+    Ok(journal
+        .read::<HistoryEntry>()?
+        .into_iter()
+        .filter(|e| e.capture.is_some())
+        .max_by_key(|e| e.requested_at))
+}
+
+pub fn history_journal(profile: &Profile) -> Journal {
+    Journal::new(profile.dir().join("history.jsonl"))
+}
+
+/// `date` 에 출근 요청이 성공한 적이 있는지 (트레이, CLI, 예약 모두 포함)
+pub fn checked_in_on(journal: &Journal, date: NaiveDate) -> std::io::Result<bool> {
+    Ok(journal
+        .read::<HistoryEntry>()?
+        .iter()
+        .any(|e| e.success && e.action == "checkIn" && e.requested_at.date_naive() == date))
+}
+
+/// `from` 부터 `to` 까지 (양 끝 포함) 요청한 기록을 시간순으로 돌려줍니다.
+pub fn entries_between(
+    journal: &Journal,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> std::io::Result<Vec<HistoryEntry>> {
+    let mut entries: Vec<HistoryEntry> = journal
+        .read::<HistoryEntry>()?
+        .into_iter()
+        .filter(|e| {
+            let date = e.requested_at.date_naive();
+            from <= date && date <= to
+        })
+        .collect();
+    entries.sort_by_key(|e| e.requested_at);
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn entry(y: i32, m: u32, d: u32, h: u32, min: u32, s: u32) -> HistoryEntry {
+        HistoryEntry {
+            requested_at: Local
+                .with_ymd_and_hms(y, m, d, h, min, s)
+                .earliest()
+                .unwrap(),
+            action: "checkIn".to_string(),
+            check_time: None,
+            success: true,
+            message: String::new(),
+            capture: None,
+        }
+    }
+
+    #[test]
+    fn entries_between_includes_both_whole_days() {
+        let dir = std::env::temp_dir().join(format!("hiworks-history-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let journal = Journal::new(dir.join("history.jsonl"));
+
+        let before = entry(2026, 10, 4, 23, 59, 59);
+        let first = entry(2026, 10, 5, 0, 0, 0);
+        let middle = entry(2026, 10, 6, 12, 0, 0);
+        let last = entry(2026, 10, 7, 23, 59, 59);
+        let after = entry(2026, 10, 8, 0, 0, 0);
+        // 기록 순서와 관계없이 시간순으로 정렬
+        for e in [&after, &last, &before, &middle, &first] {
+            journal.append(e).unwrap();
+        }
+
+        let from = NaiveDate::from_ymd_opt(2026, 10, 5).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 10, 7).unwrap();
+        assert_eq!(
+            entries_between(&journal, from, to).unwrap(),
+            [first, middle, last.clone()]
+        );
+        assert_eq!(entries_between(&journal, to, to).unwrap(), [last]);
+        assert!(entries_between(&journal, to, from).unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn only_successful_check_in_counts() {
+        let dir = std::env::temp_dir().join(format!("hiworks-checked-in-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let journal = Journal::new(dir.join("history.jsonl"));
+        let today = NaiveDate::from_ymd_opt(2026, 10, 5).unwrap();
+        assert!(!checked_in_on(&journal, today).unwrap());
+
+        let mut failed = entry(2026, 10, 5, 9, 0, 0);
+        failed.success = false;
+        let mut meeting = entry(2026, 10, 5, 9, 5, 0);
+        meeting.action = "setMeeting".to_string();
+        journal.append(&failed).unwrap();
+        journal.append(&meeting).unwrap();
+        journal.append(&entry(2026, 10, 4, 9, 0, 0)).unwrap();
+        assert!(!checked_in_on(&journal, today).unwrap());
+
+        journal.append(&entry(2026, 10, 5, 9, 10, 0)).unwrap();
+        assert!(checked_in_on(&journal, today).unwrap());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
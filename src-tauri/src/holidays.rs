@@ -0,0 +1,331 @@
+use crate::config::{self, DayOffConfig};
+use crate::playwright::PlaywrightError;
+use crate::profile::Profile;
+use crate::protocol::{LeaveDay, LeavePeriod};
+use crate::worker::WorkerHandle;
+use crate::AppState;
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use thiserror::Error;
+
+/// 앱에 포함된 한국 공휴일 (대체공휴일, 임시공휴일, 선거일 포함)
+const BUNDLED: &str = include_str!("../data/holidays-kr.json");
+
+/// 휴가 내역이 이보다 오래되면 다시 읽음
+const LEAVE_MAX_AGE: ChronoDuration = ChronoDuration::hours(6);
+
+/// 휴가 내역이 오래됐는지 확인하는 간격
+const TICK: Duration = Duration::from_secs(10 * 60);
+
+/// 휴가를 읽어두는 기간 (오늘 기준)
+const LEAVE_PAST_DAYS: i64 = 7;
+const LEAVE_FUTURE_DAYS: i64 = 90;
+
+#[derive(Error, Debug)]
+pub enum HolidayError {
+    #[error("공휴일 파일 오류: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("공휴일 파일 파싱 오류: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("공휴일 파일에 항목이 없습니다")]
+    Empty,
+}
+
+/// `[{"date": "2026-01-01", "name": "신정"}]` 형식의 공휴일 하나
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+/// 앱에 포함된 공휴일을 새 자료로 바꿀 때 쓰는 파일
+pub fn update_path() -> PathBuf {
+    config::config_dir().join("holidays.json")
+}
+
+fn read_update() -> Vec<Holiday> {
+    match std::fs::read_to_string(update_path()) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "공휴일 업데이트 파일을 읽을 수 없습니다");
+            Vec::new()
+        }),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(e) => {
+            tracing::warn!(error = %e, "공휴일 업데이트 파일을 읽을 수 없습니다");
+            Vec::new()
+        }
+    }
+}
+
+/// 업데이트 파일에 있는 연도는 앱에 포함된 목록 대신 업데이트 파일의 목록을 씁니다.
+/// 같은 날 공휴일이 겹치면 이름을 이어 붙입니다.
+pub fn merge(bundled: Vec<Holiday>, update: Vec<Holiday>) -> BTreeMap<NaiveDate, String> {
+    let years: HashSet<i32> = update.iter().map(|h| h.date.year()).collect();
+    let mut holidays = BTreeMap::new();
+    for holiday in bundled
+        .into_iter()
+        .filter(|h| !years.contains(&h.date.year()))
+        .chain(update)
+    {
+        holidays
+            .entry(holiday.date)
+            .and_modify(|name: &mut String| {
+                name.push_str(", ");
+                name.push_str(&holiday.name);
+            })
+            .or_insert(holiday.name);
+    }
+    holidays
+}
+
+pub fn holidays() -> BTreeMap<NaiveDate, String> {
+    let bundled = serde_json::from_str(BUNDLED).expect("앱에 포함된 공휴일 파일이 올바르지 않습니다");
+    merge(bundled, read_update())
+}
+
+/// 공휴일 파일을 검사해 업데이트 파일에 반영하고, 바뀐 연도를 돌려줍니다.
+/// 파일에 없는 연도의 이전 업데이트는 그대로 둡니다.
+pub fn import(path: &Path) -> Result<Vec<i32>, HolidayError> {
+    let imported: Vec<Holiday> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+    if imported.is_empty() {
+        return Err(HolidayError::Empty);
+    }
+
+    let mut years: Vec<i32> = imported.iter().map(|h| h.date.year()).collect();
+    years.sort();
+    years.dedup();
+
+    let mut update: Vec<Holiday> = read_update()
+        .into_iter()
+        .filter(|h| !years.contains(&h.date.year()))
+        .chain(imported)
+        .collect();
+    update.sort_by_key(|h| h.date);
+
+    config::write_atomic(&update_path(), &serde_json::to_string_pretty(&update)?)?;
+    Ok(years)
+}
+
+/// Hiworks 에서 마지막으로 읽어온 승인된 휴가
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaveCache {
+    pub updated_at: DateTime<Local>,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub days: Vec<LeaveDay>,
+}
+
+pub fn leave_path(profile: &Profile) -> PathBuf {
+    profile.dir().join("leave.json")
+}
+
+pub fn load_leave(profile: &Profile) -> Option<LeaveCache> {
+    let contents = std::fs::read_to_string(leave_path(profile)).ok()?;
+    serde_json::from_str(&contents).ok()
+}
+
+/// 오늘 앞뒤 기간의 승인된 휴가를 읽어 저장합니다.
+pub async fn refresh_leave(
+    worker: &WorkerHandle,
+    profile: &Profile,
+) -> Result<LeaveCache, PlaywrightError> {
+    let today = Local::now().date_naive();
+    let from = today - ChronoDuration::days(LEAVE_PAST_DAYS);
+    let to = today + ChronoDuration::days(LEAVE_FUTURE_DAYS);
+
+    let cache = LeaveCache {
+        updated_at: Local::now(),
+        from,
+        to,
+        days: worker.approved_leave(from, to).await?,
+    };
+    let contents = serde_json::to_string_pretty(&cache)?;
+    config::write_atomic(&leave_path(profile), &contents)?;
+    Ok(cache)
+}
+
+/// 출퇴근하지 않는 날 (또는 반나절)
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DayOff {
+    Holiday { name: String },
+    Leave(LeaveDay),
+}
+
+impl DayOff {
+    /// 오후 반차는 출근해야 함
+    pub fn skips_check_in(&self) -> bool {
+        !matches!(self, DayOff::Leave(leave) if leave.period == LeavePeriod::Afternoon)
+    }
+
+    /// 오전 반차는 퇴근해야 함
+    pub fn skips_check_out(&self) -> bool {
+        !matches!(self, DayOff::Leave(leave) if leave.period == LeavePeriod::Morning)
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            DayOff::Holiday { name } => name.clone(),
+            DayOff::Leave(leave) => match leave.period {
+                LeavePeriod::Full => leave.kind.clone(),
+                LeavePeriod::Morning => format!("{} (오전)", leave.kind),
+                LeavePeriod::Afternoon => format!("{} (오후)", leave.kind),
+            },
+        }
+    }
+}
+
+/// 공휴일과 승인된 휴가
+#[derive(Default)]
+pub struct DaysOff {
+    holidays: BTreeMap<NaiveDate, String>,
+    leave: Vec<LeaveDay>,
+}
+
+impl DaysOff {
+    pub fn new(holidays: BTreeMap<NaiveDate, String>, leave: Vec<LeaveDay>) -> Self {
+        Self { holidays, leave }
+    }
+
+    /// 설정에서 켠 것만 읽습니다.
+    pub fn load(profile: &Profile, config: &DayOffConfig) -> Self {
+        let holidays = if config.public_holidays {
+            holidays()
+        } else {
+            BTreeMap::new()
+        };
+        let leave = if config.approved_leave {
+            load_leave(profile).map(|c| c.days).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        Self::new(holidays, leave)
+    }
+
+    pub fn day_off(&self, date: NaiveDate) -> Option<DayOff> {
+        if let Some(name) = self.holidays.get(&date) {
+            return Some(DayOff::Holiday { name: name.clone() });
+        }
+
+        let leave: Vec<&LeaveDay> = self.leave.iter().filter(|l| l.date == date).collect();
+        let has = |period| leave.iter().any(|l| l.period == period);
+        // 오전/오후 반차를 둘 다 냈으면 하루 전체
+        let full = has(LeavePeriod::Full) || (has(LeavePeriod::Morning) && has(LeavePeriod::Afternoon));
+
+        let first = (*leave.first()?).clone();
+        Some(DayOff::Leave(if full {
+            LeaveDay {
+                period: LeavePeriod::Full,
+                ..first
+            }
+        } else {
+            first
+        }))
+    }
+}
+
+/// 승인된 휴가를 주기적으로 Hiworks 에서 읽어 둡니다.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let state = app.state::<AppState>();
+            let config = state.config.get();
+
+            let stale = load_leave(&state.profile)
+                .is_none_or(|cache| Local::now() - cache.updated_at >= LEAVE_MAX_AGE);
+            if config.day_off.approved_leave && config.has_company_url() && stale {
+                if let Err(e) = refresh_leave(&state.worker, &state.profile).await {
+                    tracing::warn!(error = %e, "휴가 내역을 읽을 수 없습니다");
+                }
+            }
+
+            tokio::time::sleep(TICK).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn holiday(d: &str, name: &str) -> Holiday {
+        Holiday {
+            date: date(d),
+            name: name.to_string(),
+        }
+    }
+
+    fn leave(d: &str, period: LeavePeriod) -> LeaveDay {
+        LeaveDay {
+            date: date(d),
+            kind: "연차".to_string(),
+            period,
+        }
+    }
+
+    #[test]
+    fn bundled_dataset_parses() {
+        let bundled: Vec<Holiday> = serde_json::from_str(BUNDLED).unwrap();
+        let holidays = merge(bundled, Vec::new());
+
+        assert_eq!(holidays.get(&date("2026-09-25")).map(String::as_str), Some("추석"));
+        assert_eq!(
+            holidays.get(&date("2025-05-05")).map(String::as_str),
+            Some("어린이날, 부처님오신날")
+        );
+        // 대체공휴일
+        assert!(holidays.contains_key(&date("2026-10-05")));
+    }
+
+    #[test]
+    fn update_replaces_whole_years() {
+        let bundled = vec![
+            holiday("2026-01-01", "신정"),
+            holiday("2026-03-02", "대체공휴일"),
+            holiday("2027-01-01", "신정"),
+        ];
+        let update = vec![holiday("2026-01-01", "신정"), holiday("2026-07-17", "제헌절")];
+
+        let holidays = merge(bundled, update);
+        let dates: Vec<String> = holidays.keys().map(|d| d.to_string()).collect();
+        assert_eq!(dates, vec!["2026-01-01", "2026-07-17", "2027-01-01"]);
+    }
+
+    #[test]
+    fn day_off_prefers_holiday_and_merges_half_days() {
+        let days_off = DaysOff::new(
+            merge(vec![holiday("2026-10-09", "한글날")], Vec::new()),
+            vec![
+                leave("2026-10-09", LeavePeriod::Full),
+                leave("2026-10-12", LeavePeriod::Morning),
+                leave("2026-10-13", LeavePeriod::Afternoon),
+                leave("2026-10-14", LeavePeriod::Morning),
+                leave("2026-10-14", LeavePeriod::Afternoon),
+            ],
+        );
+
+        let holiday = days_off.day_off(date("2026-10-09")).unwrap();
+        assert_eq!(holiday.describe(), "한글날");
+
+        let morning = days_off.day_off(date("2026-10-12")).unwrap();
+        assert!(morning.skips_check_in() && !morning.skips_check_out());
+
+        let afternoon = days_off.day_off(date("2026-10-13")).unwrap();
+        assert!(!afternoon.skips_check_in() && afternoon.skips_check_out());
+        assert_eq!(afternoon.describe(), "연차 (오후)");
+
+        let both = days_off.day_off(date("2026-10-14")).unwrap();
+        assert!(both.skips_check_in() && both.skips_check_out());
+
+        assert_eq!(days_off.day_off(date("2026-10-15")), None);
+    }
+}
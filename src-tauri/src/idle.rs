@@ -0,0 +1,470 @@
+use crate::protocol::{WorkStatus, WorkerRequest};
+use crate::{AppState, StatusSwitch, WorkerAction};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+use thiserror::Error;
+use tokio::sync::mpsc::Receiver;
+
+/// 상태 변경 알림을 받고 있어도 설정과 상태를 다시 확인하는 간격
+const WATCH_RECHECK: Duration = Duration::from_secs(5 * 60);
+
+/// 자리 비움으로 바꿀 때의 알림
+const AWAY: WorkerAction = WorkerAction {
+    request: WorkerRequest::SetStatus(WorkStatus::Out),
+    title: "자리 비움 - 외출로 변경",
+    tray_status: "외출중",
+    default_msg: "외출 처리됨",
+    check_url: false,
+};
+
+/// 돌아왔을 때의 알림
+const BACK: WorkerAction = WorkerAction {
+    request: WorkerRequest::SetStatus(WorkStatus::Work),
+    title: "복귀 - 업무로 변경",
+    tray_status: "업무중",
+    default_msg: "업무 상태로 변경됨",
+    check_url: false,
+};
+
+#[derive(Error, Debug)]
+pub enum IdleError {
+    #[cfg(target_os = "linux")]
+    #[error("D-Bus 오류: {0}")]
+    DBus(#[from] zbus::Error),
+    #[error("세션 정보를 읽을 수 없습니다: {0}")]
+    Unavailable(String),
+}
+
+/// 로그인 세션의 잠금/유휴 상태
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SessionState {
+    pub locked: bool,
+    /// 입력이 없기 시작한 시각, 사용 중이면 `None`
+    pub idle_since: Option<SystemTime>,
+}
+
+/// 화면 잠금과 유휴 상태를 알려주는 플랫폼별 구현
+pub trait SessionBackend: Send + Sync {
+    fn state(&self) -> Result<SessionState, IdleError>;
+
+    /// 상태가 바뀔 때마다 신호를 보내는 채널. `None` 이면 주기적으로 확인합니다.
+    fn changes(&self) -> Result<Option<Receiver<()>>, IdleError> {
+        Ok(None)
+    }
+}
+
+/// 테스트용 백엔드. `set` 으로 세션 상태를 바꿉니다.
+#[derive(Default)]
+pub struct FakeSession {
+    state: std::sync::Mutex<SessionState>,
+    watchers: std::sync::Mutex<Vec<tokio::sync::mpsc::Sender<()>>>,
+}
+
+impl FakeSession {
+    pub fn set(&self, state: SessionState) {
+        *self.state.lock().unwrap_or_else(|e| e.into_inner()) = state;
+        let watchers = self.watchers.lock().unwrap_or_else(|e| e.into_inner());
+        for tx in watchers.iter() {
+            let _ = tx.try_send(());
+        }
+    }
+}
+
+impl SessionBackend for FakeSession {
+    fn state(&self) -> Result<SessionState, IdleError> {
+        Ok(*self.state.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn changes(&self) -> Result<Option<Receiver<()>>, IdleError> {
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        self.watchers
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(tx);
+        Ok(Some(rx))
+    }
+}
+
+/// logind 의 현재 세션 (`session/auto`) 힌트를 읽습니다.
+#[cfg(target_os = "linux")]
+pub struct LogindBackend;
+
+#[cfg(target_os = "linux")]
+impl LogindBackend {
+    const SERVICE: &'static str = "org.freedesktop.login1";
+
+    fn connect() -> Result<zbus::blocking::Connection, IdleError> {
+        zbus::blocking::Connection::system()
+            .map_err(|e| IdleError::Unavailable(format!("시스템 D-Bus 연결 실패 ({})", e)))
+    }
+
+    fn session(
+        conn: &zbus::blocking::Connection,
+    ) -> Result<zbus::blocking::Proxy<'static>, zbus::Error> {
+        zbus::blocking::Proxy::new(
+            conn,
+            Self::SERVICE,
+            "/org/freedesktop/login1/session/auto",
+            "org.freedesktop.login1.Session",
+        )
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl SessionBackend for LogindBackend {
+    fn state(&self) -> Result<SessionState, IdleError> {
+        let conn = Self::connect()?;
+        let session = Self::session(&conn)?;
+
+        let locked: bool = session.get_property("LockedHint")?;
+        let idle: bool = session.get_property("IdleHint")?;
+        let idle_since = if idle {
+            // 1970-01-01 부터의 마이크로초
+            let micros: u64 = session.get_property("IdleSinceHint")?;
+            Some(SystemTime::UNIX_EPOCH + Duration::from_micros(micros))
+        } else {
+            None
+        };
+
+        Ok(SessionState { locked, idle_since })
+    }
+
+    /// 세션의 `Lock`/`Unlock` 시그널과 `LockedHint`/`IdleHint` 의 `PropertiesChanged` 를 받습니다.
+    fn changes(&self) -> Result<Option<Receiver<()>>, IdleError> {
+        use zbus::zvariant::OwnedObjectPath;
+
+        let conn = Self::connect()?;
+        // 시그널은 `session/auto` 가 아닌 실제 세션 경로에서 옴
+        let id: String = Self::session(&conn)?.get_property("Id")?;
+        let manager = zbus::blocking::Proxy::new(
+            &conn,
+            Self::SERVICE,
+            "/org/freedesktop/login1",
+            "org.freedesktop.login1.Manager",
+        )?;
+        let path: OwnedObjectPath = manager.call("GetSession", &(id,))?;
+
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .sender(Self::SERVICE)?
+            .path(path)?
+            .build();
+        let signals = zbus::blocking::MessageIterator::for_match_rule(rule, &conn, Some(16))?;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        std::thread::spawn(move || {
+            for signal in signals {
+                if let Err(e) = signal {
+                    tracing::warn!(error = %e, "세션 시그널 오류");
+                    continue;
+                }
+                // 이미 알림이 쌓여 있으면 한 번만 확인하면 됨
+                if let Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) = tx.try_send(()) {
+                    break;
+                }
+            }
+        });
+        Ok(Some(rx))
+    }
+}
+
+/// 이 플랫폼에서 쓸 수 있는 백엔드
+pub fn default_backend() -> Option<Arc<dyn SessionBackend>> {
+    #[cfg(target_os = "linux")]
+    {
+        Some(Arc::new(LogindBackend))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IdleEvent {
+    /// 잠금/유휴가 기준 시간을 넘김
+    Away,
+    /// 잠금을 풀거나 다시 입력함
+    Back,
+}
+
+/// 자리 비움과 복귀를 감지합니다.
+#[derive(Default)]
+pub struct IdleMonitor {
+    locked_since: Option<SystemTime>,
+    inactive_since: Option<SystemTime>,
+    away: bool,
+}
+
+impl IdleMonitor {
+    pub fn observe(
+        &mut self,
+        state: SessionState,
+        threshold: Duration,
+        now: SystemTime,
+    ) -> Option<IdleEvent> {
+        // logind 는 잠근 시각을 알려주지 않으므로 처음 본 시각을 씀
+        self.locked_since = if state.locked {
+            self.locked_since.or(Some(now))
+        } else {
+            None
+        };
+
+        let inactive_since = match (self.locked_since, state.idle_since) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        self.inactive_since = inactive_since;
+
+        match inactive_since {
+            Some(since) if !self.away => {
+                let inactive = now.duration_since(since).unwrap_or_default();
+                if inactive < threshold {
+                    return None;
+                }
+                self.away = true;
+                Some(IdleEvent::Away)
+            }
+            None if self.away => {
+                self.away = false;
+                Some(IdleEvent::Back)
+            }
+            _ => None,
+        }
+    }
+
+    /// 이때까지 돌아오지 않으면 자리 비움이 됩니다. 기다릴 것이 없으면 `None`
+    pub fn away_at(&self, threshold: Duration) -> Option<SystemTime> {
+        if self.away {
+            return None;
+        }
+        self.inactive_since.map(|since| since + threshold)
+    }
+}
+
+/// 상태 변경 알림을 받으면 자리 비움이 될 시각까지만, 아니면 `poll` 간격으로 기다립니다.
+fn next_wait(
+    monitor: &IdleMonitor,
+    watching: bool,
+    threshold: Duration,
+    poll: Duration,
+    now: SystemTime,
+) -> Duration {
+    if !watching {
+        return poll;
+    }
+    monitor
+        .away_at(threshold)
+        .map(|at| at.duration_since(now).unwrap_or_default())
+        .unwrap_or(WATCH_RECHECK)
+        .clamp(Duration::from_secs(1), WATCH_RECHECK)
+}
+
+/// 상태 변경 알림이나 `timeout` 중 먼저 오는 것을 기다립니다.
+/// 알림 채널이 끊기면 `changes` 를 비우고 주기적인 확인으로 돌아갑니다.
+async fn wait_for_change(changes: &mut Option<Receiver<()>>, timeout: Duration) {
+    let Some(rx) = changes else {
+        tokio::time::sleep(timeout).await;
+        return;
+    };
+    if let Ok(None) = tokio::time::timeout(timeout, rx.recv()).await {
+        tracing::warn!("세션 상태 알림이 끊겨 주기적으로 확인합니다");
+        *changes = None;
+    }
+}
+
+/// 업무 중일 때만 외출로 바꾸고, 직접 바꾼 경우에만 업무로 되돌립니다.
+async fn on_event(app: &AppHandle, event: IdleEvent, switch: &mut StatusSwitch) {
+    match event {
+        IdleEvent::Away => switch.apply(app, &AWAY).await,
+        IdleEvent::Back => switch.restore(app, &BACK).await,
+    }
+}
+
+/// 세션 상태 변경 알림을 구독합니다. 받을 수 없으면 `None` 이라 주기적으로 확인합니다.
+async fn subscribe(backend: &Arc<dyn SessionBackend>) -> Option<Receiver<()>> {
+    let backend = backend.clone();
+    match tauri::async_runtime::spawn_blocking(move || backend.changes()).await {
+        Ok(Ok(changes)) => changes,
+        Ok(Err(e)) => {
+            tracing::warn!(error = %e, "세션 상태 알림을 받을 수 없어 주기적으로 확인합니다");
+            None
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "세션 상태 알림 구독 실패");
+            None
+        }
+    }
+}
+
+/// 화면을 잠그고 자리를 비우면 외출로, 돌아오면 업무로 바꿉니다.
+pub fn spawn(app: AppHandle, backend: Arc<dyn SessionBackend>) {
+    tauri::async_runtime::spawn(async move {
+        let mut monitor = IdleMonitor::default();
+        let mut switch = StatusSwitch::default();
+        let mut changes = None;
+        // 구독은 켤 때 한 번만 시도함
+        let mut subscribed = false;
+
+        loop {
+            let idle = app.state::<AppState>().config.get().idle;
+            let interval = Duration::from_secs(idle.poll_seconds.max(5));
+            let threshold = Duration::from_secs(idle.threshold_minutes * 60);
+
+            if idle.enabled {
+                if !subscribed {
+                    changes = subscribe(&backend).await;
+                    subscribed = true;
+                }
+                let backend = backend.clone();
+                let state = tauri::async_runtime::spawn_blocking(move || backend.state()).await;
+                match state {
+                    Ok(Ok(state)) => {
+                        if let Some(event) = monitor.observe(state, threshold, SystemTime::now()) {
+                            on_event(&app, event, &mut switch).await;
+                        }
+                    }
+                    Ok(Err(e)) => tracing::warn!("{}", e),
+                    Err(e) => tracing::warn!(error = %e, "세션 확인 실패"),
+                }
+            } else {
+                monitor = IdleMonitor::default();
+                switch = StatusSwitch::default();
+                changes = None;
+                subscribed = false;
+            }
+
+            let wait = next_wait(
+                &monitor,
+                changes.is_some(),
+                threshold,
+                interval,
+                SystemTime::now(),
+            );
+            wait_for_change(&mut changes, wait).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Duration = Duration::from_secs(30 * 60);
+
+    fn at(minutes: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1_800_000_000 + minutes * 60)
+    }
+
+    #[test]
+    fn lock_longer_than_threshold_is_away() {
+        let backend = FakeSession::default();
+        let mut monitor = IdleMonitor::default();
+        let mut poll = |state: SessionState, minutes| {
+            backend.set(state);
+            monitor.observe(backend.state().unwrap(), THRESHOLD, at(minutes))
+        };
+
+        let locked = SessionState {
+            locked: true,
+            idle_since: None,
+        };
+        assert_eq!(poll(SessionState::default(), 0), None);
+        assert_eq!(poll(locked, 1), None);
+        assert_eq!(poll(locked, 20), None);
+        assert_eq!(poll(locked, 31), Some(IdleEvent::Away));
+        assert_eq!(poll(locked, 40), None);
+        assert_eq!(poll(SessionState::default(), 60), Some(IdleEvent::Back));
+        assert_eq!(poll(SessionState::default(), 61), None);
+    }
+
+    #[test]
+    fn short_lock_is_ignored() {
+        let mut monitor = IdleMonitor::default();
+        let locked = SessionState {
+            locked: true,
+            idle_since: None,
+        };
+        assert_eq!(monitor.observe(locked, THRESHOLD, at(0)), None);
+        assert_eq!(monitor.observe(SessionState::default(), THRESHOLD, at(10)), None);
+        // 다시 잠그면 처음부터 셈
+        assert_eq!(monitor.observe(locked, THRESHOLD, at(20)), None);
+        assert_eq!(monitor.observe(locked, THRESHOLD, at(45)), None);
+    }
+
+    #[test]
+    fn idle_hint_uses_reported_start() {
+        let mut monitor = IdleMonitor::default();
+        let idle = SessionState {
+            locked: false,
+            idle_since: Some(at(0)),
+        };
+        assert_eq!(monitor.observe(idle, THRESHOLD, at(29)), None);
+        assert_eq!(monitor.observe(idle, THRESHOLD, at(30)), Some(IdleEvent::Away));
+    }
+
+    #[test]
+    fn waits_only_until_away_when_watching() {
+        let poll = Duration::from_secs(15);
+        let mut monitor = IdleMonitor::default();
+        let locked = SessionState {
+            locked: true,
+            idle_since: None,
+        };
+        let wait = |monitor: &IdleMonitor, watching, minutes| {
+            next_wait(monitor, watching, THRESHOLD, poll, at(minutes))
+        };
+
+        assert_eq!(
+            monitor.observe(SessionState::default(), THRESHOLD, at(0)),
+            None
+        );
+        assert_eq!(monitor.away_at(THRESHOLD), None);
+        assert_eq!(wait(&monitor, true, 0), WATCH_RECHECK);
+        assert_eq!(wait(&monitor, false, 0), poll);
+
+        // 잠근 뒤에는 자리 비움이 될 시각에 다시 확인
+        assert_eq!(monitor.observe(locked, THRESHOLD, at(1)), None);
+        assert_eq!(monitor.away_at(THRESHOLD), Some(at(31)));
+        assert_eq!(wait(&monitor, true, 20), WATCH_RECHECK);
+        assert_eq!(wait(&monitor, true, 29), Duration::from_secs(2 * 60));
+
+        assert_eq!(
+            monitor.observe(locked, THRESHOLD, at(31)),
+            Some(IdleEvent::Away)
+        );
+        assert_eq!(monitor.away_at(THRESHOLD), None);
+    }
+
+    #[test]
+    fn change_wakes_the_wait() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let session = FakeSession::default();
+            let mut changes = session.changes().unwrap();
+
+            session.set(SessionState {
+                locked: true,
+                idle_since: None,
+            });
+            let started = std::time::Instant::now();
+            wait_for_change(&mut changes, Duration::from_secs(10)).await;
+            assert!(started.elapsed() < Duration::from_secs(1));
+
+            // 바뀐 것이 없으면 제한 시간까지 기다림
+            let started = std::time::Instant::now();
+            wait_for_change(&mut changes, Duration::from_millis(50)).await;
+            assert!(started.elapsed() >= Duration::from_millis(50));
+            assert!(changes.is_some());
+
+            // 알림이 끊기면 주기적인 확인으로 돌아감
+            drop(session);
+            wait_for_change(&mut changes, Duration::from_secs(10)).await;
+            assert!(changes.is_none());
+        });
+    }
+}
@@ -0,0 +1,110 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// 한 줄에 JSON 하나씩 덧붙이기만 하는 기록 파일
+///
+/// 기존 줄을 고쳐 쓰지 않으므로 앱이 도중에 종료돼도 이전 기록은 남습니다.
+/// 읽을 때 깨진 줄은 건너뜁니다.
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append<T: Serialize>(&self, entry: &T) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())
+    }
+
+    pub fn read<T: DeserializeOwned>(&self) -> std::io::Result<Vec<T>> {
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        let mut entries = Vec::new();
+        for line in BufReader::new(file).lines() {
+            if let Ok(entry) = serde_json::from_str(&line?) {
+                entries.push(entry);
+            }
+        }
+        Ok(entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Line {
+        n: u32,
+    }
+
+    fn temp_journal(name: &str) -> Journal {
+        let dir =
+            std::env::temp_dir().join(format!("hiworks-journal-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        Journal::new(dir.join("nested").join("log.jsonl"))
+    }
+
+    #[test]
+    fn append_adds_one_line_per_entry() {
+        let journal = temp_journal("append");
+        assert!(journal.read::<Line>().unwrap().is_empty());
+
+        journal.append(&Line { n: 1 }).unwrap();
+        journal.append(&Line { n: 2 }).unwrap();
+
+        let text = std::fs::read_to_string(journal.path()).unwrap();
+        assert_eq!(text, "{\"n\":1}\n{\"n\":2}\n");
+        assert_eq!(
+            journal.read::<Line>().unwrap(),
+            [Line { n: 1 }, Line { n: 2 }]
+        );
+
+        let _ = std::fs::remove_dir_all(journal.path().parent().unwrap().parent().unwrap());
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped() {
+        let journal = temp_journal("malformed");
+        journal.append(&Line { n: 1 }).unwrap();
+        // 쓰는 도중 종료돼 잘린 줄, 빈 줄, 형식이 다른 줄
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(journal.path())
+            .unwrap();
+        file.write_all(b"{\"n\":\n\n{\"other\":true}\n").unwrap();
+        journal.append(&Line { n: 2 }).unwrap();
+
+        assert_eq!(
+            journal.read::<Line>().unwrap(),
+            [Line { n: 1 }, Line { n: 2 }]
+        );
+
+        let _ = std::fs::remove_dir_all(journal.path().parent().unwrap().parent().unwrap());
+    }
+}
@@ -1,16 +1,55 @@
-mod playwright;
-
-use playwright::PlaywrightWorker;
-use std::sync::Mutex;
+mod calendar;
+pub mod cli;
+pub mod config;
+mod control;
+pub mod credentials;
+mod diagnostics;
+pub mod export;
+pub mod history;
+mod holidays;
+mod idle;
+pub mod journal;
+pub mod logging;
+mod network;
+mod notification;
+pub mod playwright;
+mod poller;
+pub mod profile;
+pub mod protocol;
+mod reminder;
+mod report;
+mod scheduler;
+mod worker;
+
+use config::{CaptureConfig, ConfigStore, DayOffConfig, ScheduleConfig};
+use chrono::NaiveDate;
+use credentials::{CredentialStore, KeyringStore, DEFAULT_ACCOUNT};
+use export::ExportFormat;
+use history::HistoryEntry;
+use journal::Journal;
+use poller::{PollerState, TrayView};
+use profile::{AppLock, Profile, ProfileCredentials, Profiles};
+use protocol::{AttendanceStatus, WorkStatus, WorkerRequest};
+use report::{ReportPeriod, WorkReport};
+use std::sync::{Arc, Mutex};
 use tauri::{
-    menu::{Menu, MenuItem, PredefinedMenuItem, Submenu},
+    menu::{CheckMenuItem, IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{TrayIcon, TrayIconBuilder},
     AppHandle, Manager, State, WebviewWindowBuilder,
 };
 use tauri_plugin_notification::NotificationExt;
+use worker::WorkerHandle;
 
 struct TrayState {
     tray: Mutex<Option<TrayIcon>>,
+    menu: Mutex<Option<TrayMenu>>,
+}
+
+/// 근태 상태에 따라 켜고 끄는 트레이 메뉴 항목
+struct TrayMenu {
+    check_in: MenuItem,
+    check_out: MenuItem,
+    status: Submenu,
 }
 
 fn update_tray_title(app: &AppHandle, status: &str) {
@@ -23,248 +62,631 @@ fn update_tray_title(app: &AppHandle, status: &str) {
     }
 }
 
+fn apply_tray_view(app: &AppHandle, view: &TrayView) {
+    let Some(state) = app.try_state::<TrayState>() else {
+        return;
+    };
+
+    if let Ok(guard) = state.tray.lock() {
+        if let Some(ref tray) = *guard {
+            let _ = tray.set_title(Some(&view.title));
+        }
+    }
+
+    if let Ok(guard) = state.menu.lock() {
+        if let Some(ref menu) = *guard {
+            let _ = menu.check_in.set_enabled(view.can_check_in);
+            let _ = menu.check_out.set_enabled(view.can_check_out);
+            let _ = menu.status.set_enabled(view.can_change_status);
+        }
+    }
+}
+
 fn notify(app: &AppHandle, title: &str, body: &str) {
     let _ = app.notification().builder().title(title).body(body).show();
 }
 
-fn handle_worker_action(
-    app: AppHandle,
-    cmd: &'static str,
+/// 트레이 메뉴나 예약에서 실행하는 워커 액션
+struct WorkerAction {
+    request: WorkerRequest,
     title: &'static str,
     tray_status: &'static str,
     default_msg: &'static str,
     check_url: bool,
-) {
+}
+
+const CHECK_IN: WorkerAction = WorkerAction {
+    request: WorkerRequest::CheckIn,
+    title: "Hiworks 출근",
+    tray_status: "근무중",
+    default_msg: "출근 완료",
+    check_url: true,
+};
+
+const CHECK_OUT: WorkerAction = WorkerAction {
+    request: WorkerRequest::CheckOut,
+    title: "Hiworks 퇴근",
+    tray_status: "퇴근",
+    default_msg: "퇴근 완료",
+    check_url: false,
+};
+
+const SET_WORK: WorkerAction = WorkerAction {
+    request: WorkerRequest::SetStatus(WorkStatus::Work),
+    title: "Hiworks 업무",
+    tray_status: "업무중",
+    default_msg: "업무 상태로 변경됨",
+    check_url: false,
+};
+
+const GO_OUT: WorkerAction = WorkerAction {
+    request: WorkerRequest::SetStatus(WorkStatus::Out),
+    title: "Hiworks 외출",
+    tray_status: "외출중",
+    default_msg: "외출 처리됨",
+    check_url: false,
+};
+
+const SET_MEETING: WorkerAction = WorkerAction {
+    request: WorkerRequest::SetStatus(WorkStatus::Meeting),
+    title: "Hiworks 회의",
+    tray_status: "회의중",
+    default_msg: "회의 상태로 변경됨",
+    check_url: false,
+};
+
+const SET_OUTWORK: WorkerAction = WorkerAction {
+    request: WorkerRequest::SetStatus(WorkStatus::Outwork),
+    title: "Hiworks 외근",
+    tray_status: "외근중",
+    default_msg: "외근 상태로 변경됨",
+    check_url: false,
+};
+
+const OPEN_LOGIN: WorkerAction = WorkerAction {
+    request: WorkerRequest::OpenLogin,
+    title: "Hiworks",
+    tray_status: "👋",
+    default_msg: "브라우저가 열렸습니다. 로그인해주세요.",
+    check_url: true,
+};
+
+/// 워커 액션을 실행하고 결과를 알림과 트레이 제목에 반영합니다.
+async fn run_worker_action(app: &AppHandle, action: &WorkerAction) -> Result<String, String> {
+    let state = app.state::<AppState>();
+
+    if action.check_url && !state.config.get().has_company_url() {
+        let msg = "먼저 설정에서 회사 URL을 입력해주세요";
+        notify(app, "Hiworks", msg);
+        show_settings_window(app);
+        return Err(msg.to_string());
+    }
+
+    match state.worker.run(action.request).await {
+        Ok(reply) => {
+            let msg = if reply.message.is_empty() {
+                action.default_msg.to_string()
+            } else {
+                reply.message
+            };
+            update_tray_title(app, action.tray_status);
+            notify(app, action.title, &msg);
+            // 로그인 중인 브라우저 페이지를 건드리지 않도록 openLogin 뒤에는 조회하지 않음
+            if action.request != WorkerRequest::OpenLogin {
+                poller::request_refresh(app);
+            }
+            Ok(msg)
+        }
+        Err(e) => {
+            let msg = format!("오류: {}", e);
+            if e.capture().is_some() {
+                // 데스크톱 알림에는 버튼을 달 수 없어 트레이 메뉴로 안내
+                let body = format!("{}\n실패 화면을 저장했습니다 (로그 → 최근 실패 화면 열기)", msg);
+                notify(app, "Hiworks", &body);
+            } else {
+                notify(app, "Hiworks", &msg);
+            }
+            Err(msg)
+        }
+    }
+}
+
+/// 자리 비움, 캘린더 회의처럼 잠시 바꿨다가 업무로 되돌리는 근무 상태
+#[derive(Default)]
+struct StatusSwitch {
+    /// 직접 바꿨으므로 되돌려야 함
+    switched: bool,
+}
+
+impl StatusSwitch {
+    /// 출근 뒤 업무 중일 때만 바꿉니다. 사용자가 고른 외출/회의/외근은 건드리지 않습니다.
+    async fn apply(&mut self, app: &AppHandle, action: &WorkerAction) {
+        let status = app.state::<AppState>().worker.status().await;
+        let working = status.is_ok_and(|s| {
+            s.check_in.is_some()
+                && s.check_out.is_none()
+                && s.status.as_deref().is_none_or(|s| s.starts_with("업무"))
+        });
+        if working {
+            self.switched = run_worker_action(app, action).await.is_ok();
+        }
+    }
+
+    /// `apply` 로 바꾼 경우에만 되돌립니다.
+    async fn restore(&mut self, app: &AppHandle, action: &WorkerAction) {
+        if std::mem::take(&mut self.switched) {
+            let _ = run_worker_action(app, action).await;
+        }
+    }
+}
+
+/// 근무 시간 보고서를 만들어 요약을 알림으로 보여줍니다.
+fn handle_report(app: AppHandle, period: ReportPeriod) {
     tauri::async_runtime::spawn(async move {
         let state = app.state::<AppState>();
-        let mut worker = state.worker.lock().unwrap();
-
-        if check_url {
-            match worker.send_command("getCompanyUrl", serde_json::json!({})) {
-                Ok(url) if url.is_null() || url.as_str() == Some("") => {
-                    notify(&app, "Hiworks", "먼저 설정에서 회사 URL을 입력해주세요");
-                    drop(worker);
-                    show_settings_window(&app);
-                    return;
-                }
-                Err(e) => {
-                    notify(&app, "Hiworks", &format!("오류: {}", e));
-                    return;
-                }
-                _ => {}
-            }
+        let title = match period {
+            ReportPeriod::Week => "이번 주 근무 시간",
+            ReportPeriod::Month => "이번 달 근무 시간",
+        };
+
+        let rules = state.config.get().report;
+        let today = chrono::Local::now().date_naive();
+        match report::fetch(&state.worker, &rules, period, today).await {
+            Ok(report) => notify(&app, title, &report.summary()),
+            Err(e) => notify(&app, "Hiworks", &format!("오류: {}", e)),
         }
+    });
+}
 
-        match worker.send_command(cmd, serde_json::json!({})) {
-            Ok(result) => {
-                let msg = result
-                    .get("message")
-                    .and_then(|m| m.as_str())
-                    .unwrap_or(default_msg);
-                update_tray_title(&app, tray_status);
-                notify(&app, title, msg);
-            }
-            Err(e) => {
-                notify(&app, "Hiworks", &format!("오류: {}", e));
-            }
+/// 파일이나 폴더를 기본 앱 (Finder, 탐색기 등) 으로 엽니다.
+fn open_path(path: &std::path::Path) -> std::io::Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = std::process::Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = std::process::Command::new("explorer");
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = std::process::Command::new("xdg-open");
+
+    let mut child = command.arg(path).spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// 로그 폴더를 열거나, 로그를 하나로 모아 다운로드 폴더에 저장합니다.
+fn handle_logs(app: &AppHandle, export: bool) {
+    let result = if export {
+        logging::export(&logging::export_dir()).and_then(|path| {
+            notify(app, "로그 내보내기", &format!("{} 에 저장했습니다", path.display()));
+            open_path(path.parent().unwrap_or(&path))
+        })
+    } else {
+        std::fs::create_dir_all(logging::log_dir()).and_then(|_| open_path(&logging::log_dir()))
+    };
+    if let Err(e) = result {
+        notify(app, "Hiworks", &format!("오류: {}", e));
+    }
+}
+
+/// 화면 캡처가 남은 가장 최근 실패의 스크린샷을 엽니다. HTML 은 같은 폴더에 있습니다.
+fn open_last_capture(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let capture = history::last_capture(&state.history)
+        .map_err(|e| e.to_string())?
+        .and_then(|entry| entry.capture)
+        .ok_or("저장된 실패 화면이 없습니다")?;
+    open_path(&capture.screenshot).map_err(|e| e.to_string())
+}
+
+/// 설치, 설정, 로그인 상태를 확인하고 지원 요청용 zip 을 만들어 폴더를 엽니다.
+fn handle_diagnostics(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let state = app.state::<AppState>();
+        let report = diagnostics::run(&state).await;
+        for check in report.problems() {
+            tracing::warn!(check = %check.name, detail = %check.detail, "진단 항목 확인 필요");
+        }
+
+        let result = diagnostics::write_bundle(&report, &state.profile, &logging::export_dir())
+            .and_then(|path| {
+                notify(
+                    &app,
+                    "진단",
+                    &format!("{}\n{} 에 저장했습니다", report.summary(), path.display()),
+                );
+                open_path(path.parent().unwrap_or(&path))
+            });
+        if let Err(e) = result {
+            notify(&app, "Hiworks", &format!("오류: {}", e));
         }
     });
 }
 
+/// 진행 중인 명령이 오래 걸리면 기다리지 않고 워커를 정리합니다.
+async fn stop_worker(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let _ = tokio::time::timeout(std::time::Duration::from_secs(3), state.worker.stop()).await;
+}
+
+/// 사용할 프로필을 저장하고 앱을 다시 시작합니다.
+/// 워커, 예약, 감지 기능이 모두 새 프로필 설정으로 다시 뜹니다.
+async fn restart_with_profile(app: &AppHandle, id: &str) -> Result<(), String> {
+    let path = profile::profiles_path();
+    let mut profiles = Profiles::load(&path).map_err(|e| e.to_string())?;
+    if profiles.active == id {
+        return Ok(());
+    }
+    profiles.set_active(id).map_err(|e| e.to_string())?;
+    profiles.save(&path).map_err(|e| e.to_string())?;
+
+    stop_worker(app).await;
+    app.restart();
+}
+
+fn handle_worker_action(app: AppHandle, action: &'static WorkerAction) {
+    tauri::async_runtime::spawn(async move {
+        let _ = run_worker_action(&app, action).await;
+    });
+}
+
 struct AppState {
-    worker: Mutex<PlaywrightWorker>,
+    worker: WorkerHandle,
+    config: Arc<ConfigStore>,
+    credentials: Arc<dyn CredentialStore>,
+    history: Arc<Journal>,
+    /// 이 실행에서 쓰는 프로필, 바꾸려면 앱을 다시 시작
+    profile: Profile,
+}
+
+/// `profile` 이 없거나 사용 중인 프로필이면 실행 중인 설정을, 아니면 그 프로필의 파일을 씁니다.
+fn profile_stores(
+    state: &AppState,
+    profile: Option<String>,
+) -> Result<(Arc<ConfigStore>, Arc<dyn CredentialStore>), String> {
+    match profile {
+        Some(id) if id != state.profile.id => {
+            let profiles = Profiles::load(&profile::profiles_path()).map_err(|e| e.to_string())?;
+            let profile = profiles.get(&id).map_err(|e| e.to_string())?;
+            let config = ConfigStore::load(profile.config_path()).map_err(|e| e.to_string())?;
+            let credentials = ProfileCredentials::new(Arc::new(KeyringStore), profile);
+            Ok((Arc::new(config), Arc::new(credentials)))
+        }
+        _ => Ok((state.config.clone(), state.credentials.clone())),
+    }
 }
 
 #[tauri::command]
-async fn set_company_url(state: State<'_, AppState>, url: String) -> Result<String, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("setCompanyUrl", serde_json::json!({ "url": url }))
+async fn set_company_url(
+    state: State<'_, AppState>,
+    url: String,
+    profile: Option<String>,
+) -> Result<String, String> {
+    let url = config::validate_company_url(&url).map_err(|e| e.to_string())?;
+    let (config, _) = profile_stores(&state, profile)?;
+    config
+        .update(|c| c.company_url = Some(url))
         .map_err(|e| e.to_string())?;
 
-    Ok(result
-        .as_str()
-        .unwrap_or("URL이 설정되었습니다")
-        .to_string())
+    Ok("회사 URL이 설정되었습니다".to_string())
 }
 
 #[tauri::command]
-async fn get_company_url(state: State<'_, AppState>) -> Result<Option<String>, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("getCompanyUrl", serde_json::json!({}))
-        .map_err(|e| e.to_string())?;
-
-    Ok(result.as_str().map(|s| s.to_string()))
+async fn get_company_url(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<Option<String>, String> {
+    let (config, _) = profile_stores(&state, profile)?;
+    Ok(config.get().company_url)
 }
 
 #[tauri::command]
-async fn set_username(state: State<'_, AppState>, username: String) -> Result<String, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("setUsername", serde_json::json!({ "username": username }))
+async fn set_username(
+    state: State<'_, AppState>,
+    username: String,
+    profile: Option<String>,
+) -> Result<String, String> {
+    let (config, _) = profile_stores(&state, profile)?;
+    config
+        .update(|c| c.username = Some(username))
         .map_err(|e| e.to_string())?;
 
-    Ok(result
-        .as_str()
-        .unwrap_or("아이디가 저장되었습니다")
-        .to_string())
+    Ok("아이디가 저장되었습니다".to_string())
 }
 
 #[tauri::command]
-async fn get_username(state: State<'_, AppState>) -> Result<Option<String>, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("getUsername", serde_json::json!({}))
-        .map_err(|e| e.to_string())?;
+async fn get_username(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<Option<String>, String> {
+    let (config, _) = profile_stores(&state, profile)?;
+    Ok(config.get().username)
+}
 
-    Ok(result.as_str().map(|s| s.to_string()))
+#[tauri::command]
+async fn get_schedule(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<ScheduleConfig, String> {
+    let (config, _) = profile_stores(&state, profile)?;
+    Ok(config.get().schedule)
 }
 
 #[tauri::command]
-async fn set_password(state: State<'_, AppState>, password: String) -> Result<String, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("setPassword", serde_json::json!({ "password": password }))
+async fn set_schedule(
+    state: State<'_, AppState>,
+    schedule: ScheduleConfig,
+    profile: Option<String>,
+) -> Result<String, String> {
+    let (config, _) = profile_stores(&state, profile)?;
+    config
+        .update(|c| c.schedule = schedule)
         .map_err(|e| e.to_string())?;
 
-    Ok(result
-        .as_str()
-        .unwrap_or("비밀번호가 저장되었습니다")
-        .to_string())
+    Ok("예약이 저장되었습니다".to_string())
 }
 
 #[tauri::command]
-async fn has_password(state: State<'_, AppState>) -> Result<bool, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("hasPassword", serde_json::json!({}))
+async fn set_password(
+    state: State<'_, AppState>,
+    password: String,
+    profile: Option<String>,
+) -> Result<String, String> {
+    let (_, credentials) = profile_stores(&state, profile)?;
+    credentials
+        .set(DEFAULT_ACCOUNT, &password)
         .map_err(|e| e.to_string())?;
 
-    Ok(result.as_bool().unwrap_or(false))
+    Ok("비밀번호가 저장되었습니다".to_string())
 }
 
 #[tauri::command]
-async fn open_login(state: State<'_, AppState>) -> Result<String, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("openLogin", serde_json::json!({}))
+async fn has_password(state: State<'_, AppState>, profile: Option<String>) -> Result<bool, String> {
+    let (_, credentials) = profile_stores(&state, profile)?;
+    let password = credentials
+        .get(DEFAULT_ACCOUNT)
         .map_err(|e| e.to_string())?;
 
-    Ok(result
-        .as_str()
-        .unwrap_or("브라우저가 열렸습니다")
-        .to_string())
+    Ok(password.is_some())
 }
 
 #[tauri::command]
-async fn check_in(state: State<'_, AppState>) -> Result<String, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("checkIn", serde_json::json!({}))
+async fn list_profiles() -> Result<Profiles, String> {
+    Profiles::load(&profile::profiles_path()).map_err(|e| e.to_string())
+}
+
+/// 새 프로필을 목록에 더합니다. 설정은 `profile` 인자로 각 명령에서 채웁니다.
+#[tauri::command]
+async fn add_profile(id: String, name: Option<String>) -> Result<Profile, String> {
+    let path = profile::profiles_path();
+    let mut profiles = Profiles::load(&path).map_err(|e| e.to_string())?;
+    let profile = profiles
+        .add(&id, name.as_deref())
         .map_err(|e| e.to_string())?;
+    profiles.save(&path).map_err(|e| e.to_string())?;
+    Ok(profile)
+}
 
-    if let Some(msg) = result.get("message").and_then(|m| m.as_str()) {
-        return Ok(msg.to_string());
-    }
+#[tauri::command]
+async fn remove_profile(id: String) -> Result<String, String> {
+    let path = profile::profiles_path();
+    let mut profiles = Profiles::load(&path).map_err(|e| e.to_string())?;
+    let profile = profiles.remove(&id).map_err(|e| e.to_string())?;
+    profiles.save(&path).map_err(|e| e.to_string())?;
+    Ok(format!("{} 프로필을 지웠습니다", profile.name))
+}
 
-    Ok(result
-        .as_str()
-        .unwrap_or("출근 처리됨")
-        .to_string())
+#[tauri::command]
+async fn switch_profile(app: AppHandle, id: String) -> Result<(), String> {
+    restart_with_profile(&app, &id).await
 }
 
 #[tauri::command]
-async fn check_out(state: State<'_, AppState>) -> Result<String, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("checkOut", serde_json::json!({}))
+async fn get_day_off(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<DayOffConfig, String> {
+    let (config, _) = profile_stores(&state, profile)?;
+    Ok(config.get().day_off)
+}
+
+#[tauri::command]
+async fn set_day_off(
+    state: State<'_, AppState>,
+    day_off: DayOffConfig,
+    profile: Option<String>,
+) -> Result<String, String> {
+    let (config, _) = profile_stores(&state, profile)?;
+    config
+        .update(|c| c.day_off = day_off)
         .map_err(|e| e.to_string())?;
 
-    if let Some(msg) = result.get("message").and_then(|m| m.as_str()) {
-        return Ok(msg.to_string());
-    }
+    Ok("휴일 설정이 저장되었습니다".to_string())
+}
 
-    Ok(result
-        .as_str()
-        .unwrap_or("퇴근 처리됨")
-        .to_string())
+/// 공휴일 JSON 파일을 반영합니다. 파일에 있는 연도는 앱에 포함된 목록 대신 이 파일을 씁니다.
+#[tauri::command]
+async fn import_holidays(path: String) -> Result<String, String> {
+    let years = holidays::import(std::path::Path::new(&path)).map_err(|e| e.to_string())?;
+    let years: Vec<String> = years.iter().map(i32::to_string).collect();
+    Ok(format!("{}년 공휴일을 반영했습니다", years.join(", ")))
 }
 
+/// 승인된 휴가를 Hiworks 에서 바로 다시 읽어옵니다.
 #[tauri::command]
-async fn set_work(state: State<'_, AppState>) -> Result<String, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("setWork", serde_json::json!({}))
+async fn refresh_leave(state: State<'_, AppState>) -> Result<String, String> {
+    let cache = holidays::refresh_leave(&state.worker, &state.profile)
+        .await
         .map_err(|e| e.to_string())?;
+    Ok(format!("휴가 {}일을 읽었습니다", cache.days.len()))
+}
 
-    if let Some(msg) = result.get("message").and_then(|m| m.as_str()) {
-        return Ok(msg.to_string());
+/// 기간 안의 공휴일과 휴가
+#[tauri::command]
+async fn get_days_off(
+    state: State<'_, AppState>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<(NaiveDate, holidays::DayOff)>, String> {
+    if from > to {
+        return Err("시작일이 종료일보다 늦습니다".to_string());
     }
 
-    Ok(result.as_str().unwrap_or("업무 상태로 변경됨").to_string())
+    let days_off = holidays::DaysOff::load(&state.profile, &state.config.get().day_off);
+    Ok(from
+        .iter_days()
+        .take_while(|d| *d <= to)
+        .filter_map(|d| days_off.day_off(d).map(|off| (d, off)))
+        .collect())
 }
 
+/// 로그를 다운로드 폴더에 모아 저장하고 경로를 돌려줍니다.
 #[tauri::command]
-async fn go_out(state: State<'_, AppState>) -> Result<String, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("goOut", serde_json::json!({}))
+async fn export_logs() -> Result<String, String> {
+    logging::export(&logging::export_dir())
+        .map(|path| path.display().to_string())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_capture(
+    state: State<'_, AppState>,
+    profile: Option<String>,
+) -> Result<CaptureConfig, String> {
+    let (config, _) = profile_stores(&state, profile)?;
+    Ok(config.get().capture)
+}
+
+#[tauri::command]
+async fn set_capture(
+    state: State<'_, AppState>,
+    capture: CaptureConfig,
+    profile: Option<String>,
+) -> Result<String, String> {
+    let (config, _) = profile_stores(&state, profile)?;
+    config
+        .update(|c| c.capture = capture)
         .map_err(|e| e.to_string())?;
 
-    if let Some(msg) = result.get("message").and_then(|m| m.as_str()) {
-        return Ok(msg.to_string());
-    }
+    Ok("실패 화면 저장 설정이 저장되었습니다".to_string())
+}
 
-    Ok(result.as_str().unwrap_or("외출 처리됨").to_string())
+/// 화면 캡처가 남은 가장 최근 실패 기록
+#[tauri::command]
+async fn get_last_capture(state: State<'_, AppState>) -> Result<Option<HistoryEntry>, String> {
+    history::last_capture(&state.history).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn set_meeting(state: State<'_, AppState>) -> Result<String, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("setMeeting", serde_json::json!({}))
+async fn open_capture(app: AppHandle) -> Result<(), String> {
+    open_last_capture(&app)
+}
+
+/// 진단 결과를 돌려주고, 지원 요청용 zip 을 다운로드 폴더에 저장합니다.
+#[tauri::command]
+async fn run_diagnostics(
+    state: State<'_, AppState>,
+) -> Result<(diagnostics::Report, String), String> {
+    let report = diagnostics::run(&state).await;
+    let path = diagnostics::write_bundle(&report, &state.profile, &logging::export_dir())
         .map_err(|e| e.to_string())?;
+    Ok((report, path.display().to_string()))
+}
 
-    if let Some(msg) = result.get("message").and_then(|m| m.as_str()) {
-        return Ok(msg.to_string());
-    }
+async fn run_request(state: &AppState, request: WorkerRequest) -> Result<String, String> {
+    state
+        .worker
+        .run(request)
+        .await
+        .map(|reply| reply.message)
+        .map_err(|e| e.to_string())
+}
 
-    Ok(result.as_str().unwrap_or("회의 상태로 변경됨").to_string())
+#[tauri::command]
+async fn open_login(state: State<'_, AppState>) -> Result<String, String> {
+    run_request(&state, WorkerRequest::OpenLogin).await
 }
 
 #[tauri::command]
-async fn set_outwork(state: State<'_, AppState>) -> Result<String, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("setOutwork", serde_json::json!({}))
-        .map_err(|e| e.to_string())?;
+async fn check_in(state: State<'_, AppState>) -> Result<String, String> {
+    run_request(&state, WorkerRequest::CheckIn).await
+}
 
-    if let Some(msg) = result.get("message").and_then(|m| m.as_str()) {
-        return Ok(msg.to_string());
-    }
+#[tauri::command]
+async fn check_out(state: State<'_, AppState>) -> Result<String, String> {
+    run_request(&state, WorkerRequest::CheckOut).await
+}
 
-    Ok(result.as_str().unwrap_or("외근 상태로 변경됨").to_string())
+#[tauri::command]
+async fn set_work(state: State<'_, AppState>) -> Result<String, String> {
+    run_request(&state, WorkerRequest::SetStatus(WorkStatus::Work)).await
 }
 
 #[tauri::command]
-async fn get_status(state: State<'_, AppState>) -> Result<serde_json::Value, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("getStatus", serde_json::json!({}))
-        .map_err(|e| e.to_string())?;
+async fn go_out(state: State<'_, AppState>) -> Result<String, String> {
+    run_request(&state, WorkerRequest::SetStatus(WorkStatus::Out)).await
+}
+
+#[tauri::command]
+async fn set_meeting(state: State<'_, AppState>) -> Result<String, String> {
+    run_request(&state, WorkerRequest::SetStatus(WorkStatus::Meeting)).await
+}
+
+#[tauri::command]
+async fn set_outwork(state: State<'_, AppState>) -> Result<String, String> {
+    run_request(&state, WorkerRequest::SetStatus(WorkStatus::Outwork)).await
+}
 
-    Ok(result)
+#[tauri::command]
+async fn get_status(state: State<'_, AppState>) -> Result<AttendanceStatus, String> {
+    state.worker.status().await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 async fn is_logged_in(state: State<'_, AppState>) -> Result<bool, String> {
-    let mut worker = state.worker.lock().unwrap();
-    let result = worker
-        .send_command("isLoggedIn", serde_json::json!({}))
-        .map_err(|e| e.to_string())?;
+    state.worker.is_logged_in().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn get_history(
+    state: State<'_, AppState>,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<HistoryEntry>, String> {
+    history::entries_between(&state.history, from, to).map_err(|e| e.to_string())
+}
+
+/// 기간의 출퇴근 기록을 CSV 또는 iCalendar 파일로 저장하고 경로를 돌려줍니다.
+#[tauri::command]
+async fn export_history(
+    state: State<'_, AppState>,
+    format: ExportFormat,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<String, String> {
+    if from > to {
+        return Err("시작일이 종료일보다 늦습니다".to_string());
+    }
 
-    Ok(result.as_bool().unwrap_or(false))
+    let entries = history::entries_between(&state.history, from, to).map_err(|e| e.to_string())?;
+    let contents = match format {
+        ExportFormat::Csv => export::to_csv(&entries),
+        ExportFormat::Ics => export::to_ics(&entries, chrono::Utc::now()),
+    };
+
+    let path = export::export_path(format, from, to);
+    config::write_atomic(&path, &contents).map_err(|e| e.to_string())?;
+    Ok(path.display().to_string())
+}
+
+/// `date` (기본값 오늘) 가 속한 주 또는 달의 근무 시간
+#[tauri::command]
+async fn get_work_report(
+    state: State<'_, AppState>,
+    period: ReportPeriod,
+    date: Option<NaiveDate>,
+) -> Result<WorkReport, String> {
+    let date = date.unwrap_or_else(|| chrono::Local::now().date_naive());
+    let rules = state.config.get().report;
+    report::fetch(&state.worker, &rules, period, date).await
 }
 
 fn show_settings_window(app: &AppHandle) {
@@ -274,7 +696,7 @@ fn show_settings_window(app: &AppHandle) {
     } else {
         match WebviewWindowBuilder::new(app, "settings", tauri::WebviewUrl::App("index.html".into()))
             .title("설정")
-            .inner_size(450.0, 500.0)
+            .inner_size(450.0, 680.0)
             .resizable(false)
             .center()
             .always_on_top(true)
@@ -313,11 +735,54 @@ fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
         &[&set_work_item, &go_out_item, &set_meeting_item, &set_outwork_item],
     )?;
 
+    let report_week_item = MenuItem::with_id(app, "report_week", "이번 주", true, None::<&str>)?;
+    let report_month_item = MenuItem::with_id(app, "report_month", "이번 달", true, None::<&str>)?;
+    let report_submenu = Submenu::with_items(
+        app,
+        "근무 시간",
+        true,
+        &[&report_week_item, &report_month_item],
+    )?;
+
     let separator1 = PredefinedMenuItem::separator(app)?;
     let separator2 = PredefinedMenuItem::separator(app)?;
 
+    // 프로필이 둘 이상일 때만 전환 메뉴를 보여줌
+    let profiles = Profiles::load(&profile::profiles_path()).unwrap_or_default();
+    let active = app.state::<AppState>().profile.id.clone();
+    let profile_items = profiles
+        .profiles
+        .iter()
+        .map(|p| {
+            let id = format!("profile:{}", p.id);
+            CheckMenuItem::with_id(app, id, &p.name, true, p.id == active, None::<&str>)
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let profile_refs: Vec<&dyn IsMenuItem<tauri::Wry>> = profile_items
+        .iter()
+        .map(|item| item as &dyn IsMenuItem<tauri::Wry>)
+        .collect();
+    let profile_submenu = Submenu::with_items(app, "프로필", true, &profile_refs)?;
+
     let open_login_item = MenuItem::with_id(app, "open_login", "브라우저로 로그인", true, None::<&str>)?;
     let settings_item = MenuItem::with_id(app, "settings", "설정...", true, None::<&str>)?;
+
+    let open_logs_item = MenuItem::with_id(app, "open_logs", "로그 폴더 열기", true, None::<&str>)?;
+    let export_logs_item = MenuItem::with_id(app, "export_logs", "로그 내보내기", true, None::<&str>)?;
+    let last_capture_item =
+        MenuItem::with_id(app, "last_capture", "최근 실패 화면 열기", true, None::<&str>)?;
+    let diagnostics_item = MenuItem::with_id(app, "diagnostics", "진단", true, None::<&str>)?;
+    let logs_submenu = Submenu::with_items(
+        app,
+        "로그",
+        true,
+        &[
+            &open_logs_item,
+            &export_logs_item,
+            &last_capture_item,
+            &diagnostics_item,
+        ],
+    )?;
     let quit_item = MenuItem::with_id(app, "quit", "종료", true, None::<&str>)?;
 
     let menu = Menu::with_items(
@@ -326,13 +791,18 @@ fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
             &check_in_item,
             &check_out_item,
             &status_submenu,
+            &report_submenu,
             &separator1,
             &open_login_item,
             &settings_item,
+            &logs_submenu,
             &separator2,
             &quit_item,
         ],
     )?;
+    if profile_items.len() > 1 {
+        menu.insert(&profile_submenu, 5)?;
+    }
 
     let tray = TrayIconBuilder::new()
         .title("👋")
@@ -340,33 +810,39 @@ fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
         .show_menu_on_left_click(true)
         .on_menu_event(move |app, event| {
             match event.id.as_ref() {
-                "check_in" => handle_worker_action(
-                    app.clone(), "checkIn", "Hiworks 출근", "근무중", "출근 완료", true
-                ),
-                "check_out" => handle_worker_action(
-                    app.clone(), "checkOut", "Hiworks 퇴근", "퇴근", "퇴근 완료", false
-                ),
-                "set_work" => handle_worker_action(
-                    app.clone(), "setWork", "Hiworks 업무", "업무중", "업무 상태로 변경됨", false
-                ),
-                "go_out" => handle_worker_action(
-                    app.clone(), "goOut", "Hiworks 외출", "외출중", "외출 처리됨", false
-                ),
-                "set_meeting" => handle_worker_action(
-                    app.clone(), "setMeeting", "Hiworks 회의", "회의중", "회의 상태로 변경됨", false
-                ),
-                "set_outwork" => handle_worker_action(
-                    app.clone(), "setOutwork", "Hiworks 외근", "외근중", "외근 상태로 변경됨", false
-                ),
-                "open_login" => handle_worker_action(
-                    app.clone(), "openLogin", "Hiworks", "👋", "브라우저가 열렸습니다. 로그인해주세요.", true
-                ),
+                "check_in" => handle_worker_action(app.clone(), &CHECK_IN),
+                "check_out" => handle_worker_action(app.clone(), &CHECK_OUT),
+                "set_work" => handle_worker_action(app.clone(), &SET_WORK),
+                "go_out" => handle_worker_action(app.clone(), &GO_OUT),
+                "set_meeting" => handle_worker_action(app.clone(), &SET_MEETING),
+                "set_outwork" => handle_worker_action(app.clone(), &SET_OUTWORK),
+                "open_login" => handle_worker_action(app.clone(), &OPEN_LOGIN),
+                "report_week" => handle_report(app.clone(), ReportPeriod::Week),
+                "report_month" => handle_report(app.clone(), ReportPeriod::Month),
                 "settings" => show_settings_window(app),
+                "open_logs" => handle_logs(app, false),
+                "export_logs" => handle_logs(app, true),
+                "last_capture" => {
+                    if let Err(e) = open_last_capture(app) {
+                        notify(app, "Hiworks", &e);
+                    }
+                }
+                "diagnostics" => handle_diagnostics(app.clone()),
                 "quit" => {
-                    let state = app.state::<AppState>();
-                    let mut worker = state.worker.lock().unwrap();
-                    let _ = worker.stop();
-                    app.exit(0);
+                    let app = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        stop_worker(&app).await;
+                        app.exit(0);
+                    });
+                }
+                id if id.starts_with("profile:") => {
+                    let app = app.clone();
+                    let id = id["profile:".len()..].to_string();
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = restart_with_profile(&app, &id).await {
+                            notify(&app, "Hiworks", &format!("오류: {}", e));
+                        }
+                    });
                 }
                 _ => {}
             }
@@ -377,6 +853,13 @@ fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
         if let Ok(mut guard) = state.tray.lock() {
             *guard = Some(tray);
         }
+        if let Ok(mut guard) = state.menu.lock() {
+            *guard = Some(TrayMenu {
+                check_in: check_in_item,
+                check_out: check_out_item,
+                status: status_submenu,
+            });
+        }
     }
 
     Ok(())
@@ -384,15 +867,67 @@ fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // 릴리스 빌드의 트레이 앱은 콘솔이 없으므로 파일에만 남김
+    logging::init(cfg!(debug_assertions));
+
+    // 프로필 기능 이전 config.json 은 기본 프로필 것
+    let legacy_config = config::config_path();
+    if let Err(e) = credentials::migrate_plaintext_password(&legacy_config, &KeyringStore) {
+        tracing::warn!(error = %e, "비밀번호 이전 실패");
+    }
+
+    let profile = Profiles::load(&profile::profiles_path())
+        .unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "프로필 목록을 읽을 수 없어 기본 프로필을 사용합니다");
+            Profiles::default()
+        })
+        .active()
+        .clone();
+    let credentials: Arc<dyn CredentialStore> =
+        Arc::new(ProfileCredentials::new(Arc::new(KeyringStore), &profile));
+
+    let config = Arc::new(ConfigStore::load(profile.config_path()).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "설정 파일을 읽을 수 없어 기본값을 사용합니다");
+        ConfigStore::empty(profile.config_path())
+    }));
+
+    let history = Arc::new(history::history_journal(&profile));
+
+    // 명령줄이 같은 브라우저 데이터로 워커를 띄우지 않고 제어 서버로 보내도록 표시,
+    // 앱이 끝나면 풀림
+    let _app_lock = match AppLock::acquire(&profile.app_lock_path()) {
+        Ok(Some(lock)) => Some(lock),
+        Ok(None) => {
+            tracing::warn!("이 프로필을 쓰는 트레이 앱이 이미 실행 중입니다");
+            None
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, "프로필 잠금 파일을 만들 수 없습니다");
+            None
+        }
+    };
+
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_notification::init())
         .manage(AppState {
-            worker: Mutex::new(PlaywrightWorker::new()),
+            worker: WorkerHandle::spawn(
+                config.clone(),
+                credentials.clone(),
+                history.clone(),
+                profile.browser_data_dir(),
+                profile.captures_dir(),
+            ),
+            config,
+            credentials,
+            history,
+            profile,
         })
         .manage(TrayState {
             tray: std::sync::Mutex::new(None),
+            menu: std::sync::Mutex::new(None),
         })
+        .manage(PollerState::default())
         .setup(|app| {
             #[cfg(target_os = "macos")]
             {
@@ -400,22 +935,26 @@ pub fn run() {
             }
 
             setup_tray(app.handle())?;
+            scheduler::spawn(app.handle().clone());
+            poller::spawn(app.handle().clone());
+            control::spawn(app.handle().clone());
+            match network::default_backend() {
+                Some(backend) => network::spawn(app.handle().clone(), backend),
+                None => tracing::info!("이 플랫폼에서는 네트워크 감지를 지원하지 않습니다"),
+            }
+            calendar::spawn(app.handle().clone());
+            holidays::spawn(app.handle().clone());
+            // 출근 안내와 자리 비움 감지가 같은 세션 정보를 씀
+            let session = idle::default_backend();
+            reminder::spawn(app.handle().clone(), session.clone());
+            match session {
+                Some(backend) => idle::spawn(app.handle().clone(), backend),
+                None => tracing::info!("이 플랫폼에서는 자리 비움 감지를 지원하지 않습니다"),
+            }
 
-            let app_handle = app.handle().clone();
-            tauri::async_runtime::spawn(async move {
-                let state = app_handle.state::<AppState>();
-                let mut worker = state.worker.lock().unwrap();
-
-                if let Ok(url) = worker.send_command("getCompanyUrl", serde_json::json!({})) {
-                    if url.is_null() || url.as_str() == Some("") {
-                        drop(worker);
-                        show_settings_window(&app_handle);
-                    }
-                } else {
-                    drop(worker);
-                    show_settings_window(&app_handle);
-                }
-            });
+            if !app.state::<AppState>().config.get().has_company_url() {
+                show_settings_window(app.handle());
+            }
 
             Ok(())
         })
@@ -424,6 +963,19 @@ pub fn run() {
             get_company_url,
             set_username,
             get_username,
+            get_schedule,
+            set_schedule,
+            get_day_off,
+            set_day_off,
+            import_holidays,
+            refresh_leave,
+            get_days_off,
+            export_logs,
+            run_diagnostics,
+            get_capture,
+            set_capture,
+            get_last_capture,
+            open_capture,
             set_password,
             has_password,
             open_login,
@@ -435,6 +987,13 @@ pub fn run() {
             check_out,
             get_status,
             is_logged_in,
+            get_history,
+            get_work_report,
+            export_history,
+            list_profiles,
+            add_profile,
+            remove_profile,
+            switch_profile,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
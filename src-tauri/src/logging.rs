@@ -0,0 +1,379 @@
+//! 로그 파일 (`~/.hiworks-commute/logs/app.log`) 과 비밀 값 가리기
+//!
+//! `tracing-subscriber` + `tracing-appender` 대신 작은 subscriber 를 직접 둡니다.
+//! - `tracing-appender` 는 시간 단위로만 파일을 나눕니다. 여기서는 크기 (5MB) 기준으로
+//!   `app.log.1` ~ `app.log.5` 만 남겨 디스크를 일정하게 쓰고, 진단 묶음에도 그대로 넣습니다.
+//! - 비밀번호 가리기는 메시지와 필드를 합친 한 줄 전체에 적용해야 합니다. 포맷터 계층마다
+//!   가리는 것보다 줄을 직접 만들고 (`format_event`) 한 번에 가리는 편이 빠뜨릴 곳이 없습니다.
+//! - 스팬을 쓰지 않으므로 필터/계층 구성이 필요 없습니다.
+
+use crate::config;
+use chrono::{DateTime, Local};
+use std::fmt::{self, Write as _};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// 로그 파일 하나의 최대 크기
+const MAX_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// 남겨두는 이전 로그 파일 수 (`app.log.1` ~ `app.log.5`)
+const KEEP_FILES: usize = 5;
+
+/// 로그 수준을 바꾸는 환경 변수 (`error`, `warn`, `info`, `debug`, `trace`)
+const LEVEL_ENV: &str = "HIWORKS_LOG";
+
+/// 값을 가리는 키 (`"password": "..."`, `token=...`)
+const SECRET_KEYS: [&str; 2] = ["password", "token"];
+
+const REDACTED: &str = "***";
+
+/// 로그에 남기지 않을 값, 키체인에서 읽은 비밀번호 등
+static SECRETS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+pub fn log_dir() -> PathBuf {
+    config::config_dir().join("logs")
+}
+
+pub fn log_path() -> PathBuf {
+    log_dir().join("app.log")
+}
+
+/// 이후 로그에서 이 값을 가립니다.
+pub fn register_secret(secret: &str) {
+    if secret.is_empty() {
+        return;
+    }
+    let mut secrets = SECRETS.write().unwrap_or_else(|e| e.into_inner());
+    if !secrets.iter().any(|s| s == secret) {
+        secrets.push(secret.to_string());
+    }
+}
+
+/// 비밀번호/토큰 키의 값과 등록한 비밀 값을 `***` 로 바꿉니다.
+pub fn redact(line: &str) -> String {
+    let mut redacted = redact_keys(line);
+    for secret in SECRETS.read().unwrap_or_else(|e| e.into_inner()).iter() {
+        redacted = redacted.replace(secret.as_str(), REDACTED);
+    }
+    redacted
+}
+
+fn redact_keys(line: &str) -> String {
+    // 한글 등은 그대로라서 바이트 위치가 원문과 같음
+    let lower = line.to_ascii_lowercase();
+    let bytes = line.as_bytes();
+    let skip_spaces = |mut i: usize| {
+        while i < bytes.len() && bytes[i] == b' ' {
+            i += 1;
+        }
+        i
+    };
+
+    let mut out = String::with_capacity(line.len());
+    let mut pos = 0;
+    while let Some((start, key)) = SECRET_KEYS
+        .iter()
+        .filter_map(|key| lower[pos..].find(key).map(|i| (pos + i, key)))
+        .min_by_key(|(i, _)| *i)
+    {
+        let mut i = start + key.len();
+        // `sessionPassword`, `password_hash` 처럼 이어지는 이름까지 키로 봄
+        while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+            i += 1;
+        }
+        if i < bytes.len() && bytes[i] == b'"' {
+            i += 1;
+        }
+        i = skip_spaces(i);
+        if i >= bytes.len() || !matches!(bytes[i], b':' | b'=') {
+            out.push_str(&line[pos..i]);
+            pos = i;
+            continue;
+        }
+        i = skip_spaces(i + 1);
+        if i >= bytes.len() {
+            break;
+        }
+
+        let (value_start, value_end) = if bytes[i] == b'"' {
+            let mut end = i + 1;
+            while end < bytes.len() && bytes[end] != b'"' {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+            (i + 1, end.min(bytes.len()))
+        } else {
+            let end = line[i..]
+                .find(|c: char| c.is_whitespace() || matches!(c, ',' | '}' | '&' | ';' | ')'))
+                .map_or(line.len(), |n| i + n);
+            (i, end)
+        };
+        out.push_str(&line[pos..value_start]);
+        out.push_str(REDACTED);
+        pos = value_end;
+    }
+    out.push_str(&line[pos..]);
+    out
+}
+
+/// 크기가 넘치면 `app.log` → `app.log.1` → ... 로 밀어내는 로그 파일
+pub struct RotatingFile {
+    path: PathBuf,
+    max_bytes: u64,
+    keep: usize,
+    file: Option<File>,
+    size: u64,
+}
+
+impl RotatingFile {
+    pub fn new(path: PathBuf, max_bytes: u64, keep: usize) -> Self {
+        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        Self {
+            path,
+            max_bytes,
+            keep,
+            file: None,
+            size,
+        }
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", n));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) {
+        self.file = None;
+        for n in (1..self.keep).rev() {
+            let _ = std::fs::rename(self.rotated(n), self.rotated(n + 1));
+        }
+        let _ = std::fs::rename(&self.path, self.rotated(1));
+        self.size = 0;
+    }
+
+    pub fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate();
+        }
+
+        if self.file.is_none() {
+            if let Some(dir) = self.path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            self.file = Some(
+                OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?,
+            );
+        }
+        if let Some(file) = self.file.as_mut() {
+            writeln!(file, "{}", line)?;
+            self.size += len;
+        }
+        Ok(())
+    }
+
+    /// 오래된 파일부터 현재 파일 순서로 경로를 돌려줍니다.
+    pub fn files(&self) -> Vec<PathBuf> {
+        (1..=self.keep)
+            .rev()
+            .map(|n| self.rotated(n))
+            .chain([self.path.clone()])
+            .filter(|p| p.exists())
+            .collect()
+    }
+}
+
+/// 이벤트의 `message` 와 나머지 필드를 `key=value` 로 모읍니다.
+#[derive(Default)]
+struct FieldWriter {
+    message: String,
+    fields: String,
+}
+
+impl Visit for FieldWriter {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            let _ = write!(self.fields, " {}={:?}", field.name(), value);
+        }
+    }
+}
+
+/// `2026-10-16T09:00:00.123+09:00  WARN scheduler: 메시지 key=value`
+pub fn format_event(at: DateTime<Local>, metadata: &Metadata<'_>, event: &Event<'_>) -> String {
+    let mut fields = FieldWriter::default();
+    event.record(&mut fields);
+    let target = metadata.target().rsplit("::").next().unwrap_or_default();
+    redact(&format!(
+        "{} {:>5} {}: {}{}",
+        at.format("%Y-%m-%dT%H:%M:%S%.3f%:z"),
+        metadata.level(),
+        target,
+        fields.message,
+        fields.fields
+    ))
+}
+
+/// 로그 파일에 한 줄씩 남기는 subscriber, `echo_stderr` 면 표준 에러에도 씀
+///
+/// 스팬은 쓰지 않으므로 ID 만 나눠줍니다.
+pub struct LogSubscriber {
+    max_level: Level,
+    echo_stderr: bool,
+    file: Mutex<RotatingFile>,
+    next_span: AtomicU64,
+}
+
+impl LogSubscriber {
+    pub fn new(file: RotatingFile, max_level: Level, echo_stderr: bool) -> Self {
+        Self {
+            max_level,
+            echo_stderr,
+            file: Mutex::new(file),
+            next_span: AtomicU64::new(1),
+        }
+    }
+}
+
+impl Subscriber for LogSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        // tracing 의 Level 은 자세할수록 큼
+        *metadata.level() <= self.max_level
+    }
+
+    fn max_level_hint(&self) -> Option<tracing::level_filters::LevelFilter> {
+        Some(tracing::level_filters::LevelFilter::from_level(
+            self.max_level,
+        ))
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(self.next_span.fetch_add(1, Ordering::Relaxed))
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let line = format_event(Local::now(), event.metadata(), event);
+        if self.echo_stderr {
+            eprintln!("{}", line);
+        }
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = file.write_line(&line);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+/// `~/.hiworks-commute/logs/app.log` 에 로그를 남기도록 설정합니다.
+/// `echo_stderr` 면 표준 에러에도 씁니다 (디버그 빌드, CLI `--verbose`).
+/// 이미 설정돼 있으면 아무것도 하지 않습니다.
+pub fn init(echo_stderr: bool) {
+    let level = std::env::var(LEVEL_ENV)
+        .ok()
+        .and_then(|l| l.parse::<Level>().ok())
+        .unwrap_or(Level::INFO);
+    let file = RotatingFile::new(log_path(), MAX_FILE_BYTES, KEEP_FILES);
+    let _ = tracing::subscriber::set_global_default(LogSubscriber::new(file, level, echo_stderr));
+}
+
+/// 오래된 파일부터 현재 `app.log` 까지
+pub fn log_files() -> Vec<PathBuf> {
+    RotatingFile::new(log_path(), MAX_FILE_BYTES, KEEP_FILES).files()
+}
+
+/// 이전 로그까지 하나로 이어 붙인 파일을 `dir` 에 만들고 경로를 돌려줍니다.
+pub fn export(dir: &Path) -> std::io::Result<PathBuf> {
+    let mut contents = String::new();
+    for file in log_files() {
+        // 이전 버전이 남긴 로그도 있을 수 있어 내보낼 때 한 번 더 가림
+        let text = std::fs::read_to_string(&file)?;
+        for line in text.lines() {
+            contents.push_str(&redact(line));
+            contents.push('\n');
+        }
+    }
+
+    let path = dir.join(format!(
+        "hiworks-log-{}.log",
+        Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    config::write_atomic(&path, &contents)?;
+    Ok(path)
+}
+
+/// 로그를 내보낼 폴더, 다운로드 폴더가 없으면 설정 폴더 아래 `exports`
+pub fn export_dir() -> PathBuf {
+    dirs::download_dir().unwrap_or_else(|| config::config_dir().join("exports"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_secret_keys_and_values() {
+        assert_eq!(
+            redact_keys(r#"{"action":"login","params":{"password":"p@ss \"w\"","id":1}}"#),
+            r#"{"action":"login","params":{"password":"***","id":1}}"#
+        );
+        assert_eq!(
+            redact_keys("sessionPassword = hunter2, user=kim"),
+            "sessionPassword = ***, user=kim"
+        );
+        assert_eq!(
+            redact_keys("GET /status?token=abc&x=1"),
+            "GET /status?token=***&x=1"
+        );
+        assert_eq!(
+            redact_keys("passwordInput 이 보이지 않습니다 Password:"),
+            "passwordInput 이 보이지 않습니다 Password:"
+        );
+
+        register_secret("s3cr3t-value");
+        assert_eq!(redact("입력값 s3cr3t-value 확인"), "입력값 *** 확인");
+    }
+
+    #[test]
+    fn rotates_by_size() {
+        let dir = std::env::temp_dir().join(format!("hiworks-logs-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut file = RotatingFile::new(dir.join("app.log"), 20, 2);
+
+        for line in ["first line", "second line", "third line", "fourth line"] {
+            file.write_line(line).unwrap();
+        }
+
+        let files = file.files();
+        let names: Vec<String> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(names, ["app.log.2", "app.log.1", "app.log"]);
+        assert_eq!(std::fs::read_to_string(&files[0]).unwrap(), "second line\n");
+        assert_eq!(std::fs::read_to_string(&files[2]).unwrap(), "fourth line\n");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
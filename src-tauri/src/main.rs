@@ -1,5 +1,35 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
-    hiworks_commute_lib::run()
+    // 오래된 macOS 는 Finder 로 실행할 때 -psn_ 인자를 붙임
+    let args: Vec<String> = std::env::args()
+        .skip(1)
+        .filter(|a| !a.starts_with("-psn_"))
+        .collect();
+
+    if args.is_empty() {
+        hiworks_commute_lib::run()
+    } else {
+        #[cfg(windows)]
+        attach_parent_console();
+        std::process::exit(hiworks_commute_lib::cli::run(&args))
+    }
+}
+
+/// 릴리스 빌드는 `windows_subsystem = "windows"` 라 콘솔 없이 시작하므로,
+/// 명령줄에서 실행했으면 그 콘솔에 출력이 보이도록 붙습니다.
+/// 출력을 파일로 돌렸거나 이미 콘솔이 있으면 실패해도 그대로 둡니다.
+#[cfg(windows)]
+fn attach_parent_console() {
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+
+    // SAFETY: 인자 외에 포인터를 넘기지 않는 Win32 호출
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
 }
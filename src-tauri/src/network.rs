@@ -0,0 +1,366 @@
+use crate::config::{NetworkConfig, Subnet};
+use crate::history;
+use crate::holidays::{DayOff, DaysOff};
+use crate::notification::notify_with_button;
+use crate::reminder::CHECK_OUT_BUTTON;
+use crate::{run_worker_action, AppState, CHECK_IN};
+use chrono::{Local, NaiveDate};
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use thiserror::Error;
+
+/// 사무실 밖으로 판단하기까지 연속으로 확인해야 하는 횟수 (잠깐 끊긴 Wi-Fi 무시)
+const LEAVE_CONFIRMATIONS: u32 = 2;
+
+#[derive(Error, Debug)]
+pub enum NetworkError {
+    #[cfg(target_os = "linux")]
+    #[error("D-Bus 오류: {0}")]
+    DBus(#[from] zbus::Error),
+    #[error("네트워크 정보를 읽을 수 없습니다: {0}")]
+    Unavailable(String),
+}
+
+/// 현재 연결된 네트워크
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NetworkSnapshot {
+    pub ssids: Vec<String>,
+    pub addresses: Vec<IpAddr>,
+}
+
+/// 연결된 Wi-Fi 와 IP 주소를 알려주는 플랫폼별 구현
+pub trait NetworkBackend: Send + Sync {
+    fn snapshot(&self) -> Result<NetworkSnapshot, NetworkError>;
+}
+
+/// 테스트용 백엔드. `set` 으로 연결 상태를 바꿉니다.
+#[derive(Default)]
+pub struct FakeBackend {
+    snapshot: std::sync::Mutex<NetworkSnapshot>,
+}
+
+impl FakeBackend {
+    pub fn set(&self, snapshot: NetworkSnapshot) {
+        *self.snapshot.lock().unwrap_or_else(|e| e.into_inner()) = snapshot;
+    }
+}
+
+impl NetworkBackend for FakeBackend {
+    fn snapshot(&self) -> Result<NetworkSnapshot, NetworkError> {
+        Ok(self.snapshot.lock().unwrap_or_else(|e| e.into_inner()).clone())
+    }
+}
+
+/// NetworkManager 의 활성 연결에서 SSID 와 주소를 읽습니다.
+#[cfg(target_os = "linux")]
+pub struct NetworkManagerBackend;
+
+#[cfg(target_os = "linux")]
+impl NetworkManagerBackend {
+    const SERVICE: &'static str = "org.freedesktop.NetworkManager";
+
+    fn proxy(
+        conn: &zbus::blocking::Connection,
+        path: zbus::zvariant::OwnedObjectPath,
+        interface: &'static str,
+    ) -> Result<zbus::blocking::Proxy<'static>, zbus::Error> {
+        zbus::blocking::Proxy::new(conn, Self::SERVICE, path, interface)
+    }
+
+    fn addresses(
+        conn: &zbus::blocking::Connection,
+        config: zbus::zvariant::OwnedObjectPath,
+        interface: &'static str,
+    ) -> Result<Vec<IpAddr>, zbus::Error> {
+        use std::collections::HashMap;
+        use zbus::zvariant::OwnedValue;
+
+        // "/" 는 주소가 없는 연결
+        if config.as_str() == "/" {
+            return Ok(Vec::new());
+        }
+        let data: Vec<HashMap<String, OwnedValue>> =
+            Self::proxy(conn, config, interface)?.get_property("AddressData")?;
+        Ok(data
+            .iter()
+            .filter_map(|entry| String::try_from(entry.get("address")?.try_clone().ok()?).ok())
+            .filter_map(|address| address.parse().ok())
+            .collect())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl NetworkBackend for NetworkManagerBackend {
+    fn snapshot(&self) -> Result<NetworkSnapshot, NetworkError> {
+        use zbus::zvariant::OwnedObjectPath;
+
+        let conn = zbus::blocking::Connection::system()
+            .map_err(|e| NetworkError::Unavailable(format!("시스템 D-Bus 연결 실패 ({})", e)))?;
+        let manager = Self::proxy(
+            &conn,
+            OwnedObjectPath::try_from("/org/freedesktop/NetworkManager")
+                .map_err(zbus::Error::from)?,
+            Self::SERVICE,
+        )?;
+
+        let mut snapshot = NetworkSnapshot::default();
+        let active: Vec<OwnedObjectPath> = manager.get_property("ActiveConnections")?;
+        for path in active {
+            let connection = Self::proxy(
+                &conn,
+                path,
+                "org.freedesktop.NetworkManager.Connection.Active",
+            )?;
+
+            let ip4: OwnedObjectPath = connection.get_property("Ip4Config")?;
+            let ip6: OwnedObjectPath = connection.get_property("Ip6Config")?;
+            snapshot.addresses.extend(Self::addresses(
+                &conn,
+                ip4,
+                "org.freedesktop.NetworkManager.IP4Config",
+            )?);
+            snapshot.addresses.extend(Self::addresses(
+                &conn,
+                ip6,
+                "org.freedesktop.NetworkManager.IP6Config",
+            )?);
+
+            let kind: String = connection.get_property("Type")?;
+            if kind != "802-11-wireless" {
+                continue;
+            }
+            let devices: Vec<OwnedObjectPath> = connection.get_property("Devices")?;
+            for device in devices {
+                let wireless = Self::proxy(
+                    &conn,
+                    device,
+                    "org.freedesktop.NetworkManager.Device.Wireless",
+                )?;
+                let ap: OwnedObjectPath = wireless.get_property("ActiveAccessPoint")?;
+                if ap.as_str() == "/" {
+                    continue;
+                }
+                let ssid: Vec<u8> = Self::proxy(
+                    &conn,
+                    ap,
+                    "org.freedesktop.NetworkManager.AccessPoint",
+                )?
+                .get_property("Ssid")?;
+                snapshot.ssids.push(String::from_utf8_lossy(&ssid).into_owned());
+            }
+        }
+
+        Ok(snapshot)
+    }
+}
+
+/// 이 플랫폼에서 쓸 수 있는 백엔드
+pub fn default_backend() -> Option<Arc<dyn NetworkBackend>> {
+    #[cfg(target_os = "linux")]
+    {
+        Some(Arc::new(NetworkManagerBackend))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// 설정한 SSID 에 연결돼 있거나 사무실 대역의 주소가 있으면 사무실로 봅니다.
+pub fn is_office(snapshot: &NetworkSnapshot, config: &NetworkConfig) -> bool {
+    let subnets: Vec<Subnet> = config
+        .office_subnets
+        .iter()
+        .filter_map(|s| s.parse().ok())
+        .collect();
+
+    snapshot
+        .ssids
+        .iter()
+        .any(|ssid| config.office_ssids.contains(ssid))
+        || snapshot
+            .addresses
+            .iter()
+            .any(|addr| subnets.iter().any(|s| s.contains(*addr)))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetworkEvent {
+    Arrived,
+    Left,
+}
+
+/// 사무실 안/밖 전환을 감지합니다.
+#[derive(Default)]
+pub struct NetworkMonitor {
+    at_office: Option<bool>,
+    away_polls: u32,
+}
+
+impl NetworkMonitor {
+    pub fn observe(&mut self, at_office: bool) -> Option<NetworkEvent> {
+        if at_office {
+            self.away_polls = 0;
+            let arrived = self.at_office != Some(true);
+            self.at_office = Some(true);
+            return arrived.then_some(NetworkEvent::Arrived);
+        }
+
+        // 시작할 때 사무실 밖이면 떠난 것이 아님
+        if self.at_office.is_none() {
+            self.at_office = Some(false);
+            return None;
+        }
+        if self.at_office == Some(false) {
+            return None;
+        }
+
+        self.away_polls += 1;
+        if self.away_polls < LEAVE_CONFIRMATIONS {
+            return None;
+        }
+        self.at_office = Some(false);
+        Some(NetworkEvent::Left)
+    }
+}
+
+fn day_off(app: &AppHandle, date: NaiveDate) -> Option<DayOff> {
+    let state = app.state::<AppState>();
+    DaysOff::load(&state.profile, &state.config.get().day_off).day_off(date)
+}
+
+/// 오늘 이미 출근했는지 출퇴근 기록에서 확인합니다. 앱을 다시 켜거나
+/// 트레이/CLI 로 직접 출근한 경우에도 다시 출근하지 않습니다.
+fn checked_in_today(app: &AppHandle, today: NaiveDate) -> bool {
+    history::checked_in_on(&app.state::<AppState>().history, today).unwrap_or_else(|e| {
+        tracing::warn!(error = %e, "출퇴근 기록을 읽을 수 없습니다");
+        false
+    })
+}
+
+async fn on_event(app: &AppHandle, event: NetworkEvent) {
+    let today = Local::now().date_naive();
+    let day_off = day_off(app, today);
+    match event {
+        // 공휴일/휴가에는 출근하지 않음
+        NetworkEvent::Arrived if day_off.as_ref().is_some_and(DayOff::skips_check_in) => {}
+        // 하루에 한 번만 자동 출근
+        NetworkEvent::Arrived if !checked_in_today(app, today) => {
+            let _ = run_worker_action(app, &CHECK_IN).await;
+        }
+        NetworkEvent::Arrived => {}
+        NetworkEvent::Left if day_off.as_ref().is_some_and(DayOff::skips_check_out) => {}
+        NetworkEvent::Left => {
+            let status = app.state::<AppState>().worker.status().await;
+            let working = status.is_ok_and(|s| s.check_in.is_some() && s.check_out.is_none());
+            if working {
+                // 퇴근 안내와 같은 "퇴근" 버튼을 붙임
+                notify_with_button(
+                    app,
+                    "사무실 네트워크를 벗어났습니다",
+                    "아직 퇴근 기록이 없습니다",
+                    &CHECK_OUT_BUTTON,
+                );
+            }
+        }
+    }
+}
+
+/// 사무실 네트워크에 들어오면 출근하고, 벗어나면 퇴근을 안내합니다.
+pub fn spawn(app: AppHandle, backend: Arc<dyn NetworkBackend>) {
+    tauri::async_runtime::spawn(async move {
+        let mut monitor = NetworkMonitor::default();
+
+        loop {
+            let config = app.state::<AppState>().config.get();
+            let configured = config.has_company_url();
+            let network = config.network;
+            let interval = Duration::from_secs(network.poll_seconds.max(5));
+
+            if network.enabled && configured {
+                let backend = backend.clone();
+                let snapshot = tauri::async_runtime::spawn_blocking(move || backend.snapshot()).await;
+                match snapshot {
+                    Ok(Ok(snapshot)) => {
+                        if let Some(event) = monitor.observe(is_office(&snapshot, &network)) {
+                            on_event(&app, event).await;
+                        }
+                    }
+                    Ok(Err(e)) => tracing::warn!("{}", e),
+                    Err(e) => tracing::warn!(error = %e, "네트워크 확인 실패"),
+                }
+            } else {
+                monitor = NetworkMonitor::default();
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn office_config() -> NetworkConfig {
+        NetworkConfig {
+            enabled: true,
+            office_ssids: vec!["ACME-Office".to_string()],
+            office_subnets: vec!["10.20.0.0/16".to_string()],
+            ..NetworkConfig::default()
+        }
+    }
+
+    fn snapshot(ssid: Option<&str>, address: &str) -> NetworkSnapshot {
+        NetworkSnapshot {
+            ssids: ssid.map(str::to_string).into_iter().collect(),
+            addresses: vec![address.parse().unwrap()],
+        }
+    }
+
+    #[test]
+    fn matches_ssid_or_subnet() {
+        let config = office_config();
+        assert!(is_office(&snapshot(Some("ACME-Office"), "192.168.0.3"), &config));
+        assert!(is_office(&snapshot(None, "10.20.4.7"), &config));
+        assert!(!is_office(&snapshot(Some("Cafe"), "10.21.0.1"), &config));
+    }
+
+    #[test]
+    fn reports_transitions_from_fake_backend() {
+        let backend = FakeBackend::default();
+        let config = office_config();
+        let mut monitor = NetworkMonitor::default();
+        let mut poll = |snapshot: NetworkSnapshot| {
+            backend.set(snapshot);
+            monitor.observe(is_office(&backend.snapshot().unwrap(), &config))
+        };
+
+        // 집에서 시작
+        assert_eq!(poll(snapshot(Some("Home"), "192.168.0.3")), None);
+        assert_eq!(
+            poll(snapshot(Some("ACME-Office"), "10.20.1.2")),
+            Some(NetworkEvent::Arrived)
+        );
+        assert_eq!(poll(snapshot(Some("ACME-Office"), "10.20.1.2")), None);
+
+        // 한 번 끊긴 것은 무시
+        assert_eq!(poll(NetworkSnapshot::default()), None);
+        assert_eq!(poll(snapshot(Some("ACME-Office"), "10.20.1.2")), None);
+
+        assert_eq!(poll(snapshot(Some("Home"), "192.168.0.3")), None);
+        assert_eq!(
+            poll(snapshot(Some("Home"), "192.168.0.3")),
+            Some(NetworkEvent::Left)
+        );
+        assert_eq!(poll(snapshot(Some("Home"), "192.168.0.3")), None);
+    }
+
+    #[test]
+    fn starting_at_office_counts_as_arrival() {
+        let mut monitor = NetworkMonitor::default();
+        assert_eq!(monitor.observe(true), Some(NetworkEvent::Arrived));
+    }
+}
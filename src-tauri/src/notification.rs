@@ -0,0 +1,179 @@
+use crate::{notify, WorkerAction};
+use tauri::AppHandle;
+
+/// 알림에 다는 버튼과 누르면 실행할 워커 액션
+pub struct Button {
+    pub label: &'static str,
+    /// 버튼을 그릴 수 없을 때 본문에 덧붙이는 안내
+    pub fallback: &'static str,
+    pub action: &'static WorkerAction,
+}
+
+impl Button {
+    fn plain_body(&self, body: &str) -> String {
+        format!("{}. {}", body, self.fallback)
+    }
+}
+
+/// 버튼 하나가 달린 알림을 보내고, 버튼을 누르면 워커 액션을 실행합니다.
+///
+/// tauri-plugin-notification 은 데스크톱에서 액션 버튼을 그리지 않으므로
+/// Linux 에서는 알림 서버 (org.freedesktop.Notifications) 에 직접 보내고,
+/// 버튼을 지원하지 않는 서버나 다른 데스크톱에서는 `fallback` 안내를 붙인 일반 알림을 보냅니다.
+pub fn notify_with_button(app: &AppHandle, title: &str, body: &str, button: &'static Button) {
+    #[cfg(target_os = "linux")]
+    {
+        let app = app.clone();
+        let (title, body) = (title.to_string(), body.to_string());
+        // 알림이 닫힐 때까지 시그널을 기다리므로 별도 스레드에서 보냄
+        std::thread::spawn(
+            move || match freedesktop::show(&title, &body, button.label) {
+                Ok(shown) => {
+                    if shown.clicked() {
+                        tauri::async_runtime::spawn(async move {
+                            let _ = crate::run_worker_action(&app, button.action).await;
+                        });
+                    }
+                }
+                Err(e) => {
+                    tracing::info!(error = %e, "버튼 없는 알림으로 보냅니다");
+                    notify(&app, &title, &button.plain_body(&body));
+                }
+            },
+        );
+    }
+    #[cfg(not(target_os = "linux"))]
+    notify(app, title, &button.plain_body(body));
+}
+
+#[cfg(target_os = "linux")]
+mod freedesktop {
+    use std::collections::HashMap;
+    use thiserror::Error;
+    use zbus::blocking::{Connection, MessageIterator, Proxy};
+    use zbus::zvariant::Value;
+
+    const SERVICE: &str = "org.freedesktop.Notifications";
+    const PATH: &str = "/org/freedesktop/Notifications";
+
+    #[derive(Error, Debug)]
+    pub enum NotificationError {
+        #[error("D-Bus 오류: {0}")]
+        DBus(#[from] zbus::Error),
+        #[error("알림 서버가 버튼을 지원하지 않습니다")]
+        NoActions,
+    }
+
+    /// 알림 서버가 보내는 시그널
+    #[derive(Debug, PartialEq)]
+    pub(super) enum Signal {
+        ActionInvoked(u32, String),
+        Closed(u32),
+    }
+
+    /// 이 알림에 대한 시그널이면 버튼을 눌렀는지 돌려줍니다.
+    pub(super) fn outcome(id: u32, signal: &Signal) -> Option<bool> {
+        match signal {
+            Signal::ActionInvoked(target, key) if *target == id => Some(key == BUTTON_KEY),
+            Signal::Closed(target) if *target == id => Some(false),
+            _ => None,
+        }
+    }
+
+    /// 알림 서버에 넘기는 버튼의 액션 키
+    pub(super) const BUTTON_KEY: &str = "run";
+
+    /// 화면에 띄운 알림
+    pub struct Shown {
+        id: u32,
+        signals: MessageIterator,
+    }
+
+    pub fn show(title: &str, body: &str, label: &str) -> Result<Shown, NotificationError> {
+        let conn = Connection::session()?;
+        let server = Proxy::new(&conn, SERVICE, PATH, SERVICE)?;
+
+        let capabilities: Vec<String> = server.call("GetCapabilities", &())?;
+        if !capabilities.iter().any(|c| c == "actions") {
+            return Err(NotificationError::NoActions);
+        }
+
+        // 버튼을 바로 눌러도 놓치지 않도록 알림을 보내기 전에 구독
+        let rule = zbus::MatchRule::builder()
+            .msg_type(zbus::message::Type::Signal)
+            .interface(SERVICE)?
+            .path(PATH)?
+            .build();
+        let signals = MessageIterator::for_match_rule(rule, &conn, Some(16))?;
+
+        let hints: HashMap<&str, Value> = HashMap::new();
+        let id: u32 = server.call(
+            "Notify",
+            &(
+                "Hiworks",
+                0u32,
+                "",
+                title,
+                body,
+                vec![BUTTON_KEY, label],
+                hints,
+                -1i32,
+            ),
+        )?;
+        Ok(Shown { id, signals })
+    }
+
+    impl Shown {
+        /// 버튼을 누르면 `true`, 누르지 않고 닫히면 `false`
+        pub fn clicked(self) -> bool {
+            for message in self.signals {
+                let message = match message {
+                    Ok(message) => message,
+                    Err(e) => {
+                        tracing::warn!(error = %e, "알림 시그널 오류");
+                        continue;
+                    }
+                };
+                let body = message.body();
+                let signal = match message.header().member().map(|m| m.as_str()) {
+                    Some("ActionInvoked") => body
+                        .deserialize()
+                        .ok()
+                        .map(|(id, key)| Signal::ActionInvoked(id, key)),
+                    Some("NotificationClosed") => body
+                        .deserialize::<(u32, u32)>()
+                        .ok()
+                        .map(|(id, _reason)| Signal::Closed(id)),
+                    _ => None,
+                };
+                if let Some(clicked) = signal.and_then(|s| outcome(self.id, &s)) {
+                    return clicked;
+                }
+            }
+            false
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::freedesktop::{outcome, Signal, BUTTON_KEY};
+
+    #[test]
+    fn only_this_notifications_button_counts() {
+        assert_eq!(
+            outcome(7, &Signal::ActionInvoked(7, BUTTON_KEY.to_string())),
+            Some(true)
+        );
+        assert_eq!(
+            outcome(7, &Signal::ActionInvoked(7, "default".to_string())),
+            Some(false)
+        );
+        assert_eq!(
+            outcome(7, &Signal::ActionInvoked(8, BUTTON_KEY.to_string())),
+            None
+        );
+        assert_eq!(outcome(7, &Signal::Closed(7)), Some(false));
+        assert_eq!(outcome(7, &Signal::Closed(3)), None);
+    }
+}
@@ -1,14 +1,44 @@
+use crate::protocol::FailureCapture;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
-use std::process::{Child, Command, Stdio};
+use std::process::{Child, ChildStderr, ChildStdout, Command, Stdio};
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 
 static REQUEST_ID: AtomicU64 = AtomicU64::new(1);
 
+/// 워커가 `ready` 를 알릴 때까지 기다리는 시간
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 워커 재시작 최대 시도 횟수
+const MAX_START_ATTEMPTS: u32 = 3;
+
+/// 재시작 시도 사이의 첫 대기 시간 (시도마다 두 배)
+const START_BACKOFF: Duration = Duration::from_millis(500);
+
+/// 워커가 죽었을 때 다시 보내도 안전한 (상태를 바꾸지 않는) 액션
+fn is_idempotent(action: &str) -> bool {
+    matches!(action, "getStatus" | "isLoggedIn")
+}
+
+/// 액션별 기본 응답 대기 시간
+fn default_timeout(action: &str) -> Duration {
+    match action {
+        // 브라우저를 거치지 않음
+        "isLoggedIn" | "close" => Duration::from_secs(10),
+        // 수동 로그인을 기다릴 수 있음
+        "openLogin" => Duration::from_secs(180),
+        _ => Duration::from_secs(90),
+    }
+}
+
 /// 번들 디렉토리를 찾습니다 (node, browsers, playwright-worker.js 포함)
-fn find_bundle_dir() -> Option<PathBuf> {
+pub(crate) fn find_bundle_dir() -> Option<PathBuf> {
     let exe_path = std::env::current_exe().ok()?;
     let exe_dir = exe_path.parent()?;
 
@@ -33,7 +63,7 @@ fn find_bundle_dir() -> Option<PathBuf> {
     None
 }
 
-fn find_node() -> Option<PathBuf> {
+pub(crate) fn find_node() -> Option<PathBuf> {
     // 번들된 node 우선
     if let Some(bundle_dir) = find_bundle_dir() {
         let bundled_node = bundle_dir.join("node/bin/node");
@@ -74,6 +104,26 @@ pub enum PlaywrightError {
     ScriptNotFound,
     #[error("Node.js를 찾을 수 없습니다")]
     NodeNotFound,
+    #[error("워커 응답 시간 초과: {action} ({}초)", .timeout.as_secs())]
+    Timeout { action: String, timeout: Duration },
+    #[error("워커 프로세스가 비정상 종료되었습니다")]
+    Crashed,
+    /// 실패 화면을 저장한 응답 오류
+    #[error("워커 응답 오류: {message}")]
+    Captured {
+        message: String,
+        capture: FailureCapture,
+    },
+}
+
+impl PlaywrightError {
+    /// 실패 시점의 화면 캡처가 있으면 돌려줍니다.
+    pub fn capture(&self) -> Option<&FailureCapture> {
+        match self {
+            PlaywrightError::Captured { capture, .. } => Some(capture),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -84,105 +134,326 @@ struct WorkerCommand {
 }
 
 #[derive(Deserialize, Debug)]
-#[allow(dead_code)]
 struct WorkerResponse {
     id: Option<u64>,
     success: Option<bool>,
     data: Option<serde_json::Value>,
     ready: Option<bool>,
     error: Option<String>,
+    artifacts: Option<FailureCapture>,
+}
+
+/// 워커 stdout 을 별도 스레드에서 줄 단위로 읽어 채널로 넘깁니다.
+/// 스트림이 닫히면 송신측이 drop 되어 수신측은 `Disconnected` 를 받습니다.
+fn spawn_stdout_reader(stdout: ChildStdout) -> Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut reader = BufReader::new(stdout);
+        loop {
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    rx
+}
+
+/// 워커의 표준 에러를 한 줄씩 로그로 옮깁니다.
+fn spawn_stderr_logger(stderr: ChildStderr) {
+    thread::spawn(move || {
+        for line in BufReader::new(stderr).lines() {
+            let Ok(line) = line else { break };
+            if !line.trim().is_empty() {
+                tracing::info!(target: "node", "{}", line.trim_end());
+            }
+        }
+    });
+}
+
+/// 워커 프로세스를 띄우는 방법
+///
+/// 기본값은 번들/개발 환경에서 node 와 `playwright-worker.js` 를 찾아 쓰며,
+/// 테스트에서는 다른 node 경로나 가짜 워커 스크립트를 지정할 수 있습니다.
+#[derive(Debug, Clone)]
+pub struct WorkerLaunch {
+    pub node: PathBuf,
+    pub script: PathBuf,
+    pub work_dir: PathBuf,
+    pub env: Vec<(String, String)>,
+}
+
+impl WorkerLaunch {
+    pub fn new(node: impl Into<PathBuf>, script: impl Into<PathBuf>) -> Self {
+        let script = script.into();
+        let work_dir = script
+            .parent()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("."));
+        Self {
+            node: node.into(),
+            script,
+            work_dir,
+            env: Vec::new(),
+        }
+    }
+
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// 번들 디렉토리 또는 개발 환경에서 node 와 워커 스크립트를 찾습니다.
+    pub fn discover() -> Result<Self, PlaywrightError> {
+        let (script, work_dir) =
+            find_script_and_workdir().ok_or(PlaywrightError::ScriptNotFound)?;
+        let node = find_node().ok_or(PlaywrightError::NodeNotFound)?;
+
+        let mut launch = Self {
+            node,
+            script,
+            work_dir,
+            env: Vec::new(),
+        };
+
+        // 번들된 브라우저 경로 설정
+        if let Some(bundle_dir) = find_bundle_dir() {
+            let browsers_path = bundle_dir.join("browsers");
+            if browsers_path.exists() {
+                launch = launch.env(
+                    "PLAYWRIGHT_BROWSERS_PATH",
+                    browsers_path.to_string_lossy(),
+                );
+            }
+        }
+
+        Ok(launch)
+    }
+}
+
+pub(crate) fn find_script_and_workdir() -> Option<(PathBuf, PathBuf)> {
+    // 번들 디렉토리 우선
+    if let Some(bundle_dir) = find_bundle_dir() {
+        let script = bundle_dir.join("playwright-worker.js");
+        if script.exists() {
+            return Some((script, bundle_dir));
+        }
+    }
+
+    // 개발 환경 폴백
+    let cwd = std::env::current_dir().ok()?;
+    let exe_path = std::env::current_exe().ok()?;
+    let exe_dir = exe_path.parent()?;
+
+    let candidates = vec![
+        (cwd.join("scripts/playwright-worker.js"), cwd.join("scripts")),
+        (cwd.join("../scripts/playwright-worker.js"), cwd.join("../scripts")),
+        (exe_dir.join("../Resources/scripts/playwright-worker.js"), exe_dir.join("../Resources/scripts")),
+    ];
+
+    for (script, workdir) in candidates {
+        if script.exists() {
+            return Some((script.canonicalize().ok()?, workdir.canonicalize().ok()?));
+        }
+    }
+
+    None
 }
 
 pub struct PlaywrightWorker {
+    launch: Option<WorkerLaunch>,
     process: Option<Child>,
     stdin: Option<std::process::ChildStdin>,
-    stdout_reader: Option<BufReader<std::process::ChildStdout>>,
+    stdout_rx: Option<Receiver<String>>,
+    timeouts: HashMap<String, Duration>,
+    browser_data: Option<PathBuf>,
+    capture_dir: Option<PathBuf>,
 }
 
 impl PlaywrightWorker {
     pub fn new() -> Self {
         Self {
+            launch: None,
             process: None,
             stdin: None,
-            stdout_reader: None,
+            stdout_rx: None,
+            timeouts: HashMap::new(),
+            browser_data: None,
+            capture_dir: None,
         }
     }
 
-    fn find_script_and_workdir() -> Option<(PathBuf, PathBuf)> {
-        // 번들 디렉토리 우선
-        if let Some(bundle_dir) = find_bundle_dir() {
-            let script = bundle_dir.join("playwright-worker.js");
-            if script.exists() {
-                return Some((script, bundle_dir));
+    /// 지정한 node/스크립트로 워커를 띄웁니다.
+    pub fn with_launch(launch: WorkerLaunch) -> Self {
+        let mut worker = Self::new();
+        worker.launch = Some(launch);
+        worker
+    }
+
+    /// 로그인 세션을 저장할 브라우저 데이터 디렉토리 (다음 시작부터 적용)
+    pub fn set_browser_data(&mut self, dir: PathBuf) {
+        self.browser_data = Some(dir);
+    }
+
+    /// 실패 화면을 저장할 디렉토리 (다음 시작부터 적용)
+    pub fn set_capture_dir(&mut self, dir: PathBuf) {
+        self.capture_dir = Some(dir);
+    }
+
+    /// 특정 액션의 응답 대기 시간을 지정합니다.
+    pub fn set_timeout(&mut self, action: &str, timeout: Duration) {
+        self.timeouts.insert(action.to_string(), timeout);
+    }
+
+    pub fn timeout_for(&self, action: &str) -> Duration {
+        self.timeouts
+            .get(action)
+            .copied()
+            .unwrap_or_else(|| default_timeout(action))
+    }
+
+    /// `accept` 를 만족하는 응답이 올 때까지 워커 stdout 을 읽습니다.
+    /// JSON 이 아닌 줄과 다른 요청에 대한 응답은 건너뜁니다.
+    fn read_response(
+        &self,
+        action: &str,
+        timeout: Duration,
+        accept: impl Fn(&WorkerResponse) -> bool,
+    ) -> Result<WorkerResponse, PlaywrightError> {
+        let rx = self.stdout_rx.as_ref().ok_or(PlaywrightError::NotRunning)?;
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let line = match rx.recv_timeout(remaining) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(PlaywrightError::Timeout {
+                        action: action.to_string(),
+                        timeout,
+                    })
+                }
+                Err(RecvTimeoutError::Disconnected) => return Err(PlaywrightError::Crashed),
+            };
+
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str::<WorkerResponse>(line) {
+                Ok(response) if accept(&response) => return Ok(response),
+                Ok(response) => match response.id {
+                    Some(stale_id) => {
+                        tracing::info!("지난 요청({})의 응답을 버립니다", stale_id)
+                    }
+                    None => tracing::info!("예상하지 못한 응답을 버립니다: {}", line),
+                },
+                Err(_) => tracing::info!("JSON 이 아닌 출력을 무시합니다: {}", line),
             }
         }
+    }
 
-        // 개발 환경 폴백
-        let cwd = std::env::current_dir().ok()?;
-        let exe_path = std::env::current_exe().ok()?;
-        let exe_dir = exe_path.parent()?;
+    /// 워커 프로세스가 살아있는지 확인합니다.
+    pub fn is_alive(&mut self) -> bool {
+        match self.process.as_mut() {
+            Some(process) => matches!(process.try_wait(), Ok(None)),
+            None => false,
+        }
+    }
 
-        let candidates = vec![
-            (cwd.join("scripts/playwright-worker.js"), cwd.join("scripts")),
-            (cwd.join("../scripts/playwright-worker.js"), cwd.join("../scripts")),
-            (exe_dir.join("../Resources/scripts/playwright-worker.js"), exe_dir.join("../Resources/scripts")),
-        ];
+    /// 멈춘 워커를 종료하고 새로 띄웁니다.
+    fn restart(&mut self) {
+        let _ = self.stop();
+        if let Err(e) = self.ensure_started() {
+            tracing::error!(error = %e, "재시작 실패");
+        }
+    }
 
-        for (script, workdir) in candidates {
-            if script.exists() {
-                return Some((script.canonicalize().ok()?, workdir.canonicalize().ok()?));
+    /// 워커가 떠 있도록 보장합니다. 실패하면 간격을 늘려가며 다시 시도합니다.
+    fn ensure_started(&mut self) -> Result<(), PlaywrightError> {
+        let mut attempt = 1;
+        let mut backoff = START_BACKOFF;
+        loop {
+            match self.start() {
+                Ok(()) => return Ok(()),
+                // 설치 문제는 재시도해도 해결되지 않음
+                Err(e @ (PlaywrightError::ScriptNotFound | PlaywrightError::NodeNotFound)) => {
+                    return Err(e)
+                }
+                Err(e) if attempt >= MAX_START_ATTEMPTS => return Err(e),
+                Err(e) => {
+                    tracing::warn!(
+                        error = %e,
+                        "시작 실패 ({}/{})",
+                        attempt,
+                        MAX_START_ATTEMPTS
+                    );
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                    attempt += 1;
+                }
             }
         }
-
-        None
     }
 
     pub fn start(&mut self) -> Result<(), PlaywrightError> {
         if self.process.is_some() {
-            return Ok(());
+            if self.is_alive() {
+                return Ok(());
+            }
+            // 죽은 프로세스의 파이프를 정리하고 새로 띄움
+            tracing::info!("워커 프로세스 종료 감지, 다시 시작합니다");
+            let _ = self.stop();
         }
 
-        let (script_path, work_dir) =
-            Self::find_script_and_workdir().ok_or(PlaywrightError::ScriptNotFound)?;
-
-        let node_path = find_node().ok_or(PlaywrightError::NodeNotFound)?;
+        let launch = match &self.launch {
+            Some(launch) => launch.clone(),
+            None => WorkerLaunch::discover()?,
+        };
 
-        let mut cmd = Command::new(&node_path);
-        cmd.arg(&script_path)
-            .current_dir(&work_dir)
+        let mut cmd = Command::new(&launch.node);
+        cmd.arg(&launch.script)
+            .current_dir(&launch.work_dir)
+            .envs(launch.env.iter().map(|(k, v)| (k, v)))
+            .envs(self.browser_data.iter().map(|dir| ("HIWORKS_BROWSER_DATA", dir)))
+            .envs(self.capture_dir.iter().map(|dir| ("HIWORKS_CAPTURE_DIR", dir)))
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::inherit());
-
-        // 번들된 브라우저 경로 설정
-        if let Some(bundle_dir) = find_bundle_dir() {
-            let browsers_path = bundle_dir.join("browsers");
-            if browsers_path.exists() {
-                cmd.env("PLAYWRIGHT_BROWSERS_PATH", &browsers_path);
-            }
-        }
+            .stderr(Stdio::piped());
 
         let mut child = cmd.spawn()?;
 
         let stdin = child.stdin.take().expect("Failed to get stdin");
         let stdout = child.stdout.take().expect("Failed to get stdout");
+        if let Some(stderr) = child.stderr.take() {
+            spawn_stderr_logger(stderr);
+        }
 
         self.stdin = Some(stdin);
-        self.stdout_reader = Some(BufReader::new(stdout));
+        self.stdout_rx = Some(spawn_stdout_reader(stdout));
         self.process = Some(child);
 
-        let mut line = String::new();
-        if let Some(reader) = &mut self.stdout_reader {
-            reader.read_line(&mut line)?;
-            let response: WorkerResponse = serde_json::from_str(&line)?;
-            if response.ready != Some(true) {
-                return Err(PlaywrightError::ResponseError(
+        let ready = self.read_response("start", STARTUP_TIMEOUT, |r| r.ready.is_some());
+
+        match ready {
+            Ok(response) if response.ready == Some(true) => Ok(()),
+            Ok(_) => {
+                let _ = self.stop();
+                Err(PlaywrightError::ResponseError(
                     "워커 준비 실패".to_string(),
-                ));
+                ))
+            }
+            Err(e) => {
+                let _ = self.stop();
+                Err(e)
             }
         }
-
-        Ok(())
     }
 
     pub fn send_command(
@@ -190,29 +461,54 @@ impl PlaywrightWorker {
         action: &str,
         params: serde_json::Value,
     ) -> Result<serde_json::Value, PlaywrightError> {
-        self.start()?;
+        match self.send_once(action, &params) {
+            Err(PlaywrightError::Crashed) if is_idempotent(action) => {
+                tracing::info!("{} 처리 중 워커 종료, 다시 보냅니다", action);
+                self.send_once(action, &params)
+            }
+            result => result,
+        }
+    }
+
+    fn send_once(
+        &mut self,
+        action: &str,
+        params: &serde_json::Value,
+    ) -> Result<serde_json::Value, PlaywrightError> {
+        self.ensure_started()?;
 
         let id = REQUEST_ID.fetch_add(1, Ordering::SeqCst);
         let cmd = WorkerCommand {
             id,
             action: action.to_string(),
-            params,
+            params: params.clone(),
         };
 
         let stdin = self.stdin.as_mut().ok_or(PlaywrightError::NotRunning)?;
-        let reader = self
-            .stdout_reader
-            .as_mut()
-            .ok_or(PlaywrightError::NotRunning)?;
 
         let cmd_json = serde_json::to_string(&cmd)?;
-        writeln!(stdin, "{}", cmd_json)?;
-        stdin.flush()?;
-
-        let mut line = String::new();
-        reader.read_line(&mut line)?;
+        let written = writeln!(stdin, "{}", cmd_json).and_then(|_| stdin.flush());
+        if let Err(e) = written {
+            if self.is_alive() {
+                return Err(e.into());
+            }
+            let _ = self.stop();
+            return Err(PlaywrightError::Crashed);
+        }
 
-        let response: WorkerResponse = serde_json::from_str(&line)?;
+        let timeout = self.timeout_for(action);
+        let response = match self.read_response(action, timeout, |r| r.id == Some(id)) {
+            Ok(response) => response,
+            Err(PlaywrightError::Crashed) => {
+                let _ = self.stop();
+                return Err(PlaywrightError::Crashed);
+            }
+            Err(e) => {
+                // 응답이 없는 워커는 재사용할 수 없으므로 새로 띄움
+                self.restart();
+                return Err(e);
+            }
+        };
 
         if let Some(error) = response.error {
             return Err(PlaywrightError::ResponseError(error));
@@ -223,7 +519,13 @@ impl PlaywrightWorker {
                 .data
                 .and_then(|d| d.as_str().map(|s| s.to_string()))
                 .unwrap_or_else(|| "알 수 없는 오류".to_string());
-            return Err(PlaywrightError::ResponseError(error_msg));
+            return Err(match response.artifacts {
+                Some(capture) => PlaywrightError::Captured {
+                    message: error_msg,
+                    capture,
+                },
+                None => PlaywrightError::ResponseError(error_msg),
+            });
         }
 
         Ok(response
@@ -238,14 +540,62 @@ impl PlaywrightWorker {
         }
 
         self.stdin = None;
-        self.stdout_reader = None;
+        self.stdout_rx = None;
         self.process = None;
         Ok(())
     }
 }
 
+impl Default for PlaywrightWorker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Drop for PlaywrightWorker {
     fn drop(&mut self) {
         let _ = self.stop();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 워커 stdout 대신 정해진 줄을 흘려보내는 워커
+    fn worker_reading(lines: &[&str]) -> PlaywrightWorker {
+        let (tx, rx) = mpsc::channel();
+        for line in lines {
+            tx.send(format!("{}\n", line)).unwrap();
+        }
+        let mut worker = PlaywrightWorker::new();
+        worker.stdout_rx = Some(rx);
+        worker
+    }
+
+    #[test]
+    fn read_response_skips_noise_and_stale_ids() {
+        let worker = worker_reading(&[
+            "",
+            "[playwright] browser launched",
+            r#"{"id":6,"success":true,"data":"지난 응답"}"#,
+            r#"{"success":true,"data":"id 없음"}"#,
+            r#"{"id":7,"success":true,"data":"이번 응답"}"#,
+        ]);
+
+        let response = worker
+            .read_response("getStatus", Duration::from_secs(1), |r| r.id == Some(7))
+            .unwrap();
+        assert_eq!(response.data, Some(serde_json::json!("이번 응답")));
+    }
+
+    #[test]
+    fn read_response_never_fails_on_json() {
+        // JSON 이 아닌 줄만 남기고 stdout 이 닫히면 JsonError 가 아니라 Crashed
+        let worker = worker_reading(&["<html>", "{not json"]);
+        assert!(matches!(
+            worker.read_response("getStatus", Duration::from_secs(1), |r| r.id == Some(1)),
+            Err(PlaywrightError::Crashed)
+        ));
+    }
+}
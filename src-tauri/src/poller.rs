@@ -0,0 +1,149 @@
+use crate::protocol::AttendanceStatus;
+use crate::{apply_tray_view, AppState};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+use tokio::sync::Notify;
+
+/// 폴링 간격 설정이 없을 때 사용하는 값 (분)
+pub const DEFAULT_POLL_MINUTES: u64 = 10;
+
+/// 다음 폴링 시각과 잠자기 여부를 확인하는 간격
+const TICK: Duration = Duration::from_secs(15);
+
+/// 한 틱 사이에 벽시계가 이보다 더 흘렀으면 잠자기에서 깨어난 것으로 봄
+const WAKE_THRESHOLD: Duration = Duration::from_secs(60);
+
+/// 근태 상태에 맞춘 트레이 제목과 메뉴 활성화 상태
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrayView {
+    pub title: String,
+    pub can_check_in: bool,
+    pub can_check_out: bool,
+    pub can_change_status: bool,
+}
+
+impl TrayView {
+    pub fn from_status(status: &AttendanceStatus) -> Self {
+        let working = status.check_in.is_some() && status.check_out.is_none();
+
+        let title = match (status.check_in, status.check_out) {
+            (None, _) => "👋".to_string(),
+            (Some(_), Some(_)) => "👋 퇴근".to_string(),
+            (Some(_), None) => format!("👋 {}", status.status.as_deref().unwrap_or("근무중")),
+        };
+
+        Self {
+            title,
+            can_check_in: status.check_in.is_none(),
+            can_check_out: working,
+            can_change_status: working,
+        }
+    }
+}
+
+/// 즉시 다시 폴링하도록 깨우는 신호
+#[derive(Default)]
+pub struct PollerState {
+    refresh: Notify,
+}
+
+/// 다음 틱을 기다리지 않고 상태를 다시 가져오게 합니다.
+pub fn request_refresh(app: &AppHandle) {
+    if let Some(state) = app.try_state::<PollerState>() {
+        state.refresh.notify_one();
+    }
+}
+
+async fn poll_once(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    if !state.config.get().has_company_url() {
+        return;
+    }
+
+    match state.worker.status().await {
+        Ok(status) => apply_tray_view(app, &TrayView::from_status(&status)),
+        Err(e) => tracing::warn!(error = %e, "상태 조회 실패"),
+    }
+}
+
+/// 주기적으로, 그리고 잠자기에서 깨어날 때 `getStatus` 로 트레이를 갱신합니다.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_poll: Option<SystemTime> = None;
+        let mut force = false;
+
+        loop {
+            let minutes = app
+                .state::<AppState>()
+                .config
+                .get()
+                .status_poll_minutes
+                .unwrap_or(DEFAULT_POLL_MINUTES);
+            let interval = Duration::from_secs(minutes * 60);
+
+            let due = minutes > 0
+                && last_poll.is_none_or(|t| t.elapsed().unwrap_or_default() >= interval);
+
+            if force || due {
+                poll_once(&app).await;
+                last_poll = Some(SystemTime::now());
+            }
+
+            // 잠자기 중에는 타이머가 멈추므로, 벽시계가 훨씬 더 흘렀으면 깨어난 것
+            let state = app.state::<PollerState>();
+            let slept_from = SystemTime::now();
+            force = tokio::select! {
+                _ = tokio::time::sleep(TICK) => {
+                    slept_from.elapsed().unwrap_or_default() > TICK + WAKE_THRESHOLD
+                }
+                _ = state.refresh.notified() => true,
+            };
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    fn status(check_in: Option<(u32, u32)>, check_out: Option<(u32, u32)>) -> AttendanceStatus {
+        let time = |(h, m)| NaiveTime::from_hms_opt(h, m, 0).unwrap();
+        AttendanceStatus {
+            check_in: check_in.map(time),
+            check_out: check_out.map(time),
+            status: None,
+        }
+    }
+
+    #[test]
+    fn before_check_in() {
+        let view = TrayView::from_status(&status(None, None));
+        assert_eq!(view.title, "👋");
+        assert!(view.can_check_in);
+        assert!(!view.can_check_out);
+        assert!(!view.can_change_status);
+    }
+
+    #[test]
+    fn while_working() {
+        let mut working = status(Some((9, 0)), None);
+        assert_eq!(TrayView::from_status(&working).title, "👋 근무중");
+
+        working.status = Some("회의".to_string());
+        let view = TrayView::from_status(&working);
+        assert_eq!(view.title, "👋 회의");
+        assert!(!view.can_check_in);
+        assert!(view.can_check_out);
+        assert!(view.can_change_status);
+    }
+
+    #[test]
+    fn after_check_out() {
+        let view = TrayView::from_status(&status(Some((9, 0)), Some((18, 0))));
+        assert_eq!(view.title, "👋 퇴근");
+        assert!(!view.can_check_in);
+        assert!(!view.can_check_out);
+        assert!(!view.can_change_status);
+    }
+}
@@ -0,0 +1,349 @@
+use crate::config;
+use crate::credentials::{CredentialError, CredentialStore};
+use crate::logging;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// 프로필 기능 이전부터 쓰던 설정/브라우저 데이터를 그대로 쓰는 프로필
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Error, Debug)]
+pub enum ProfileError {
+    #[error("프로필 파일 오류: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("프로필 파일 파싱 오류: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("프로필이 없습니다: {0}")]
+    NotFound(String),
+    #[error("이미 있는 프로필입니다: {0}")]
+    Duplicate(String),
+    #[error("프로필 ID 는 영문 소문자, 숫자, -, _ 로 32자까지 쓸 수 있습니다: {0}")]
+    InvalidId(String),
+    #[error("기본 프로필과 사용 중인 프로필은 지울 수 없습니다: {0}")]
+    Protected(String),
+}
+
+/// Hiworks 회사 (테넌트) 하나에 대한 설정 묶음
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Profile {
+    pub id: String,
+    /// 트레이 메뉴에 보이는 이름
+    pub name: String,
+}
+
+impl Profile {
+    pub fn is_default(&self) -> bool {
+        self.id == DEFAULT_PROFILE
+    }
+
+    /// 기본 프로필은 `~/.hiworks-commute`, 나머지는 `~/.hiworks-commute/profiles/<id>`
+    pub fn dir(&self) -> PathBuf {
+        if self.is_default() {
+            config::config_dir()
+        } else {
+            config::config_dir().join("profiles").join(&self.id)
+        }
+    }
+
+    pub fn config_path(&self) -> PathBuf {
+        self.dir().join("config.json")
+    }
+
+    /// 로그인 세션을 유지하는 Playwright 브라우저 데이터
+    pub fn browser_data_dir(&self) -> PathBuf {
+        self.dir().join("browser-data")
+    }
+
+    /// 실패한 요청의 화면 캡처
+    pub fn captures_dir(&self) -> PathBuf {
+        self.dir().join("captures")
+    }
+
+    /// 트레이 앱이 이 프로필을 쓰는 동안 잠가 두는 파일
+    pub fn app_lock_path(&self) -> PathBuf {
+        self.dir().join("app.lock")
+    }
+}
+
+/// 트레이 앱이 프로필의 브라우저 데이터를 쓰고 있다는 표시.
+/// 파일 잠금이라 앱이 비정상 종료해도 남지 않습니다.
+pub struct AppLock {
+    _file: std::fs::File,
+}
+
+impl AppLock {
+    /// 잠금을 잡습니다. 다른 프로세스가 잡고 있으면 `None`
+    pub fn acquire(path: &Path) -> Result<Option<Self>, ProfileError> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(Self { _file: file })),
+            Err(std::fs::TryLockError::WouldBlock) => Ok(None),
+            Err(std::fs::TryLockError::Error(e)) => Err(e.into()),
+        }
+    }
+
+    /// 다른 프로세스가 잠금을 잡고 있는지
+    pub fn is_held(path: &Path) -> bool {
+        std::fs::File::open(path).is_ok_and(|file| {
+            matches!(
+                file.try_lock_shared(),
+                Err(std::fs::TryLockError::WouldBlock)
+            )
+        })
+    }
+}
+
+fn validate_id(id: &str) -> Result<(), ProfileError> {
+    let valid = !id.is_empty()
+        && id.len() <= 32
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(ProfileError::InvalidId(id.to_string()))
+    }
+}
+
+/// `~/.hiworks-commute/profiles.json` 에 저장되는 프로필 목록
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Profiles {
+    /// 트레이 앱이 사용하는 프로필
+    pub active: String,
+    pub profiles: Vec<Profile>,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PROFILE.to_string(),
+            profiles: vec![Profile {
+                id: DEFAULT_PROFILE.to_string(),
+                name: "기본".to_string(),
+            }],
+        }
+    }
+}
+
+pub fn profiles_path() -> PathBuf {
+    config::config_dir().join("profiles.json")
+}
+
+impl Profiles {
+    /// 파일이 없으면 기본 프로필 하나만 있는 목록을 돌려줍니다.
+    pub fn load(path: &Path) -> Result<Self, ProfileError> {
+        let mut profiles: Profiles = match std::fs::read_to_string(path) {
+            Ok(contents) => serde_json::from_str(&contents)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        if !profiles.profiles.iter().any(Profile::is_default) {
+            profiles.profiles.insert(0, Self::default().profiles.remove(0));
+        }
+        Ok(profiles)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ProfileError> {
+        config::write_atomic(path, &serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, id: &str) -> Result<&Profile, ProfileError> {
+        self.profiles
+            .iter()
+            .find(|p| p.id == id)
+            .ok_or_else(|| ProfileError::NotFound(id.to_string()))
+    }
+
+    /// 사용 중인 프로필, 목록에서 지워졌으면 기본 프로필
+    pub fn active(&self) -> &Profile {
+        self.get(&self.active)
+            .or_else(|_| self.get(DEFAULT_PROFILE))
+            .unwrap_or(&self.profiles[0])
+    }
+
+    pub fn add(&mut self, id: &str, name: Option<&str>) -> Result<Profile, ProfileError> {
+        validate_id(id)?;
+        if self.get(id).is_ok() {
+            return Err(ProfileError::Duplicate(id.to_string()));
+        }
+        let profile = Profile {
+            id: id.to_string(),
+            name: name
+                .map(str::trim)
+                .filter(|n| !n.is_empty())
+                .unwrap_or(id)
+                .to_string(),
+        };
+        self.profiles.push(profile.clone());
+        Ok(profile)
+    }
+
+    /// 목록에서만 지웁니다. 설정 파일과 브라우저 데이터는 남겨 둡니다.
+    pub fn remove(&mut self, id: &str) -> Result<Profile, ProfileError> {
+        let profile = self.get(id)?.clone();
+        if profile.is_default() || self.active == id {
+            return Err(ProfileError::Protected(id.to_string()));
+        }
+        self.profiles.retain(|p| p.id != id);
+        Ok(profile)
+    }
+
+    pub fn set_active(&mut self, id: &str) -> Result<(), ProfileError> {
+        self.get(id)?;
+        self.active = id.to_string();
+        Ok(())
+    }
+}
+
+/// 프로필마다 키체인 계정을 나누는 저장소
+///
+/// 기본 프로필은 기존 계정 이름 (`default`, `calendar`) 을 그대로 쓰고,
+/// 다른 프로필은 `<프로필 ID>/<계정>` 으로 저장합니다.
+pub struct ProfileCredentials {
+    inner: Arc<dyn CredentialStore>,
+    prefix: Option<String>,
+}
+
+impl ProfileCredentials {
+    pub fn new(inner: Arc<dyn CredentialStore>, profile: &Profile) -> Self {
+        Self {
+            inner,
+            prefix: (!profile.is_default()).then(|| profile.id.clone()),
+        }
+    }
+
+    fn account(&self, account: &str) -> String {
+        match &self.prefix {
+            Some(prefix) => format!("{}/{}", prefix, account),
+            None => account.to_string(),
+        }
+    }
+}
+
+impl CredentialStore for ProfileCredentials {
+    fn get(&self, account: &str) -> Result<Option<String>, CredentialError> {
+        let password = self.inner.get(&self.account(account))?;
+        // 워커 오류 메시지 등에 섞여 로그에 남지 않도록
+        if let Some(password) = &password {
+            logging::register_secret(password);
+        }
+        Ok(password)
+    }
+
+    fn set(&self, account: &str, password: &str) -> Result<(), CredentialError> {
+        logging::register_secret(password);
+        self.inner.set(&self.account(account), password)
+    }
+
+    fn delete(&self, account: &str) -> Result<(), CredentialError> {
+        self.inner.delete(&self.account(account))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credentials::{MemoryStore, DEFAULT_ACCOUNT};
+
+    #[test]
+    fn adds_and_removes_profiles() {
+        let mut profiles = Profiles::default();
+        let acme = profiles.add("acme", Some(" 에이씨엠이 ")).unwrap();
+        assert_eq!(acme.name, "에이씨엠이");
+        assert_eq!(profiles.add("sub-co", None).unwrap().name, "sub-co");
+
+        assert!(matches!(profiles.add("acme", None), Err(ProfileError::Duplicate(_))));
+        for id in ["", "Acme", "a/b", "../x", &"a".repeat(33)] {
+            assert!(matches!(profiles.add(id, None), Err(ProfileError::InvalidId(_))), "{}", id);
+        }
+
+        profiles.set_active("acme").unwrap();
+        assert_eq!(profiles.active().id, "acme");
+        assert!(matches!(profiles.remove("acme"), Err(ProfileError::Protected(_))));
+        assert!(matches!(profiles.remove(DEFAULT_PROFILE), Err(ProfileError::Protected(_))));
+        assert!(matches!(profiles.set_active("nope"), Err(ProfileError::NotFound(_))));
+
+        profiles.remove("sub-co").unwrap();
+        assert_eq!(profiles.profiles.len(), 2);
+    }
+
+    #[test]
+    fn default_profile_keeps_legacy_paths() {
+        let profiles = Profiles::default();
+        let default = profiles.active();
+        assert_eq!(default.config_path(), config::config_path());
+        assert_eq!(default.browser_data_dir(), config::config_dir().join("browser-data"));
+
+        let acme = Profile {
+            id: "acme".to_string(),
+            name: "acme".to_string(),
+        };
+        assert_eq!(
+            acme.config_path(),
+            config::config_dir().join("profiles/acme/config.json")
+        );
+    }
+
+    #[test]
+    fn app_lock_is_released_on_drop() {
+        let path = std::env::temp_dir().join(format!("hiworks-app-{}.lock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        assert!(!AppLock::is_held(&path));
+
+        let lock = AppLock::acquire(&path).unwrap().unwrap();
+        assert!(AppLock::is_held(&path));
+        assert!(AppLock::acquire(&path).unwrap().is_none());
+
+        drop(lock);
+        assert!(!AppLock::is_held(&path));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn load_restores_default_profile() {
+        let path = std::env::temp_dir().join(format!("hiworks-profiles-{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"{"active": "gone", "profiles": [{"id": "acme", "name": "ACME"}]}"#,
+        )
+        .unwrap();
+
+        let profiles = Profiles::load(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(profiles.profiles[0].id, DEFAULT_PROFILE);
+        assert_eq!(profiles.active().id, DEFAULT_PROFILE);
+        assert_eq!(Profiles::load(&path).unwrap(), Profiles::default());
+    }
+
+    #[test]
+    fn credentials_are_scoped_per_profile() {
+        let store: Arc<dyn CredentialStore> = Arc::new(MemoryStore::default());
+        let profiles = {
+            let mut profiles = Profiles::default();
+            profiles.add("acme", None).unwrap();
+            profiles
+        };
+        let default = ProfileCredentials::new(store.clone(), profiles.get(DEFAULT_PROFILE).unwrap());
+        let acme = ProfileCredentials::new(store.clone(), profiles.get("acme").unwrap());
+
+        default.set(DEFAULT_ACCOUNT, "one").unwrap();
+        acme.set(DEFAULT_ACCOUNT, "two").unwrap();
+
+        assert_eq!(store.get("default").unwrap().as_deref(), Some("one"));
+        assert_eq!(store.get("acme/default").unwrap().as_deref(), Some("two"));
+        assert_eq!(acme.get(DEFAULT_ACCOUNT).unwrap().as_deref(), Some("two"));
+    }
+}
@@ -0,0 +1,318 @@
+use chrono::{NaiveDate, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 근무 상태 버튼 (`.list-btns`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WorkStatus {
+    Work,
+    Out,
+    Meeting,
+    Outwork,
+}
+
+/// `playwright-worker.js` 가 처리하는 요청
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WorkerRequest {
+    OpenLogin,
+    CheckIn,
+    CheckOut,
+    SetStatus(WorkStatus),
+    GetStatus,
+    /// 개인 근무 기록 페이지에서 기간 (양 끝 포함) 의 출퇴근 시각을 읽음
+    GetAttendanceHistory { from: NaiveDate, to: NaiveDate },
+    /// 휴가 사용 내역에서 기간 (양 끝 포함) 에 승인된 휴가를 하루 단위로 읽음
+    GetApprovedLeave { from: NaiveDate, to: NaiveDate },
+    IsLoggedIn,
+    Close,
+}
+
+impl WorkerRequest {
+    /// 파라미터 없이 보낼 수 있는 요청
+    pub const ALL: &'static [WorkerRequest] = &[
+        WorkerRequest::OpenLogin,
+        WorkerRequest::CheckIn,
+        WorkerRequest::CheckOut,
+        WorkerRequest::SetStatus(WorkStatus::Work),
+        WorkerRequest::SetStatus(WorkStatus::Out),
+        WorkerRequest::SetStatus(WorkStatus::Meeting),
+        WorkerRequest::SetStatus(WorkStatus::Outwork),
+        WorkerRequest::GetStatus,
+        WorkerRequest::IsLoggedIn,
+        WorkerRequest::Close,
+    ];
+
+    /// 워커의 `handleCommand` 에서 쓰는 action 이름
+    pub fn action(self) -> &'static str {
+        match self {
+            WorkerRequest::OpenLogin => "openLogin",
+            WorkerRequest::CheckIn => "checkIn",
+            WorkerRequest::CheckOut => "checkOut",
+            WorkerRequest::SetStatus(WorkStatus::Work) => "setWork",
+            WorkerRequest::SetStatus(WorkStatus::Out) => "goOut",
+            WorkerRequest::SetStatus(WorkStatus::Meeting) => "setMeeting",
+            WorkerRequest::SetStatus(WorkStatus::Outwork) => "setOutwork",
+            WorkerRequest::GetStatus => "getStatus",
+            WorkerRequest::GetAttendanceHistory { .. } => "getAttendanceHistory",
+            WorkerRequest::GetApprovedLeave { .. } => "getApprovedLeave",
+            WorkerRequest::IsLoggedIn => "isLoggedIn",
+            WorkerRequest::Close => "close",
+        }
+    }
+
+    pub fn from_action(action: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|r| r.action() == action)
+    }
+
+    pub fn params(self) -> serde_json::Value {
+        match self {
+            WorkerRequest::GetAttendanceHistory { from, to }
+            | WorkerRequest::GetApprovedLeave { from, to } => serde_json::json!({
+                "from": from.format("%Y-%m-%d").to_string(),
+                "to": to.format("%Y-%m-%d").to_string(),
+            }),
+            _ => serde_json::json!({}),
+        }
+    }
+
+    /// 로그인 페이지를 거칠 수 있어 계정 정보가 필요한 요청
+    pub fn needs_login(self) -> bool {
+        !matches!(self, WorkerRequest::IsLoggedIn | WorkerRequest::Close)
+    }
+}
+
+/// 출퇴근/상태 변경 요청의 응답
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", from = "RawActionReply")]
+pub struct ActionReply {
+    pub message: String,
+    /// 출퇴근 후 Hiworks 페이지의 `.check-time` 에 표시된 시각
+    pub check_time: Option<NaiveTime>,
+}
+
+/// 워커는 `message` 만 있는 응답을 문자열로, `data` 가 있으면 객체로 보냄
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawActionReply {
+    Text(String),
+    #[serde(rename_all = "camelCase")]
+    Object {
+        message: String,
+        check_time: Option<String>,
+    },
+}
+
+impl From<RawActionReply> for ActionReply {
+    fn from(raw: RawActionReply) -> Self {
+        match raw {
+            RawActionReply::Text(message) => Self {
+                message,
+                check_time: None,
+            },
+            RawActionReply::Object {
+                message,
+                check_time,
+            } => Self {
+                message,
+                check_time: check_time.as_deref().and_then(parse_check_time),
+            },
+        }
+    }
+}
+
+/// `getStatus` 응답
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", from = "RawAttendanceStatus")]
+pub struct AttendanceStatus {
+    pub check_in: Option<NaiveTime>,
+    pub check_out: Option<NaiveTime>,
+    pub status: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAttendanceStatus {
+    check_in_time: Option<String>,
+    check_out_time: Option<String>,
+    status: Option<String>,
+}
+
+impl From<RawAttendanceStatus> for AttendanceStatus {
+    fn from(raw: RawAttendanceStatus) -> Self {
+        Self {
+            check_in: raw.check_in_time.as_deref().and_then(parse_check_time),
+            check_out: raw.check_out_time.as_deref().and_then(parse_check_time),
+            status: raw
+                .status
+                .as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty() && *s != "알 수 없음")
+                .map(str::to_string),
+        }
+    }
+}
+
+/// `getAttendanceHistory` 응답의 하루치 기록
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", from = "RawAttendanceDay")]
+pub struct AttendanceDay {
+    pub date: NaiveDate,
+    pub check_in: Option<NaiveTime>,
+    pub check_out: Option<NaiveTime>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAttendanceDay {
+    date: NaiveDate,
+    check_in_time: Option<String>,
+    check_out_time: Option<String>,
+}
+
+impl From<RawAttendanceDay> for AttendanceDay {
+    fn from(raw: RawAttendanceDay) -> Self {
+        Self {
+            date: raw.date,
+            check_in: raw.check_in_time.as_deref().and_then(parse_check_time),
+            check_out: raw.check_out_time.as_deref().and_then(parse_check_time),
+        }
+    }
+}
+
+/// 하루 중 휴가로 빠지는 부분
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LeavePeriod {
+    Full,
+    /// 오전 반차
+    Morning,
+    /// 오후 반차
+    Afternoon,
+}
+
+/// `getApprovedLeave` 응답의 하루치 승인된 휴가
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaveDay {
+    pub date: NaiveDate,
+    /// 연차, 병가 등 Hiworks 에 표시된 휴가 종류
+    pub kind: String,
+    pub period: LeavePeriod,
+}
+
+/// 실패한 요청의 화면 (`capture.enabled` 일 때 워커가 `artifacts` 로 돌려줌)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FailureCapture {
+    pub screenshot: PathBuf,
+    /// 실패 시점의 페이지 HTML, 첫 줄에 URL 주석
+    pub html: PathBuf,
+}
+
+/// `.check-time` 텍스트 ("09:01", "09:01:23", "-" 등) 에서 시각을 읽습니다.
+pub fn parse_check_time(text: &str) -> Option<NaiveTime> {
+    let token = text.split_whitespace().find(|t| t.contains(':'))?;
+    NaiveTime::parse_from_str(token, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(token, "%H:%M"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `playwright-worker.js` 의 `case "..."` 와 `WorkerRequest` 가 어긋나지 않는지 확인
+    #[test]
+    fn actions_match_worker_script() {
+        let script = include_str!("../../scripts/playwright-worker.js");
+        let mut script_actions: Vec<&str> = script
+            .lines()
+            .filter_map(|l| l.trim().strip_prefix("case \""))
+            .filter_map(|l| l.split('"').next())
+            .collect();
+        script_actions.sort();
+
+        let date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let history = WorkerRequest::GetAttendanceHistory { from: date, to: date };
+        let leave = WorkerRequest::GetApprovedLeave { from: date, to: date };
+        let mut actions: Vec<&str> = WorkerRequest::ALL
+            .iter()
+            .chain([&history, &leave])
+            .map(|r| r.action())
+            .collect();
+        actions.sort();
+
+        assert_eq!(script_actions, actions);
+    }
+
+    #[test]
+    fn action_round_trip() {
+        for request in WorkerRequest::ALL {
+            assert_eq!(WorkerRequest::from_action(request.action()), Some(*request));
+        }
+        assert_eq!(WorkerRequest::from_action("setCompanyUrl"), None);
+    }
+
+    #[test]
+    fn parses_status_reply() {
+        let status: AttendanceStatus = serde_json::from_value(serde_json::json!({
+            "checkInTime": "09:01",
+            "checkOutTime": "-",
+            "status": "알 수 없음",
+        }))
+        .unwrap();
+
+        assert_eq!(status.check_in, NaiveTime::from_hms_opt(9, 1, 0));
+        assert_eq!(status.check_out, None);
+        assert_eq!(status.status, None);
+    }
+
+    #[test]
+    fn parses_action_reply() {
+        let text: ActionReply = serde_json::from_value(serde_json::json!("출근 완료")).unwrap();
+        let object: ActionReply =
+            serde_json::from_value(serde_json::json!({ "message": "출근 완료" })).unwrap();
+        assert_eq!(text, object);
+
+        let checked: ActionReply = serde_json::from_value(serde_json::json!({
+            "message": "출근 완료: 08:59",
+            "checkTime": " 08:59 ",
+        }))
+        .unwrap();
+        assert_eq!(checked.check_time, NaiveTime::from_hms_opt(8, 59, 0));
+    }
+
+    #[test]
+    fn parses_attendance_day() {
+        let day: AttendanceDay = serde_json::from_value(serde_json::json!({
+            "date": "2026-10-05",
+            "checkInTime": "08:57",
+            "checkOutTime": "",
+        }))
+        .unwrap();
+
+        assert_eq!(day.date, NaiveDate::from_ymd_opt(2026, 10, 5).unwrap());
+        assert_eq!(day.check_in, NaiveTime::from_hms_opt(8, 57, 0));
+        assert_eq!(day.check_out, None);
+    }
+
+    #[test]
+    fn parses_leave_day() {
+        let day: LeaveDay = serde_json::from_value(serde_json::json!({
+            "date": "2026-10-05",
+            "kind": "연차",
+            "period": "morning",
+        }))
+        .unwrap();
+
+        assert_eq!(day.period, LeavePeriod::Morning);
+    }
+
+    #[test]
+    fn parses_check_time_text() {
+        assert_eq!(parse_check_time("09:01:23"), NaiveTime::from_hms_opt(9, 1, 23));
+        assert_eq!(parse_check_time("출근 18:30"), NaiveTime::from_hms_opt(18, 30, 0));
+        assert_eq!(parse_check_time("-"), None);
+    }
+}
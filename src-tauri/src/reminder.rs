@@ -0,0 +1,438 @@
+use crate::config::{CheckInReminderConfig, CheckOutReminderConfig};
+use crate::holidays::{DayOff, DaysOff};
+use crate::idle::SessionBackend;
+use crate::notification::{notify_with_button, Button};
+use crate::protocol::AttendanceStatus;
+use crate::{AppState, CHECK_IN, CHECK_OUT};
+use chrono::{Datelike, Duration as ChronoDuration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Manager};
+
+/// 안내할 시각인지 확인하는 간격
+const TICK: Duration = Duration::from_secs(60);
+
+/// 출근 기록이 없을 때 상태를 다시 확인하는 간격
+const NOT_CHECKED_IN_RETRY: ChronoDuration = ChronoDuration::minutes(30);
+
+/// 이 시간 안에 입력이 있었으면 자리에 있는 것으로 봄
+const ACTIVE_WITHIN: Duration = Duration::from_secs(2 * 60);
+
+/// 퇴근 안내 알림의 "퇴근" 버튼
+pub const CHECK_OUT_BUTTON: Button = Button {
+    label: "퇴근",
+    fallback: "트레이 메뉴에서 퇴근을 눌러주세요",
+    action: &CHECK_OUT,
+};
+
+/// 출근 안내 알림의 "출근" 버튼
+pub const CHECK_IN_BUTTON: Button = Button {
+    label: "출근",
+    fallback: "트레이 메뉴에서 출근을 눌러주세요",
+    action: &CHECK_IN,
+};
+
+/// 퇴근 안내를 보낼 시각, 안내 조건이 하나도 없으면 `None`
+pub fn due_at(config: &CheckOutReminderConfig, check_in: NaiveDateTime) -> Option<NaiveDateTime> {
+    let after_check_in = config
+        .hours_after_check_in
+        .map(|h| check_in + ChronoDuration::hours(i64::from(h)));
+    let at = config.at.map(|t| check_in.date().and_time(t));
+    after_check_in.into_iter().chain(at).max()
+}
+
+/// 하루 동안 퇴근 안내를 언제 보낼지 정합니다.
+///
+/// 브라우저 워커를 자주 깨우지 않도록 안내할 수 있는 가장 이른 시각 전에는
+/// 상태를 조회하지 않고, 안내한 뒤에는 간격을 두 배씩 늘립니다.
+#[derive(Debug)]
+pub struct CheckOutReminder {
+    date: NaiveDate,
+    config: CheckOutReminderConfig,
+    next_check: Option<NaiveDateTime>,
+    sent: u32,
+}
+
+impl CheckOutReminder {
+    pub fn new(date: NaiveDate, config: CheckOutReminderConfig) -> Self {
+        Self {
+            date,
+            // 자정에 출근했어도 이 시각 전에는 안내할 일이 없음
+            next_check: due_at(&config, date.and_time(NaiveTime::MIN)),
+            config,
+            sent: 0,
+        }
+    }
+
+    /// 근태 상태를 조회해 볼 시각인지
+    pub fn should_check(&self, now: NaiveDateTime) -> bool {
+        self.next_check.is_some_and(|at| now >= at)
+    }
+
+    /// 조회한 상태를 반영하고, 지금 안내해야 하면 `true` 를 돌려줍니다.
+    pub fn observe(&mut self, now: NaiveDateTime, status: &AttendanceStatus) -> bool {
+        if status.check_out.is_some() {
+            self.next_check = None;
+            return false;
+        }
+        let Some(check_in) = status.check_in else {
+            self.next_check = Some(now + NOT_CHECKED_IN_RETRY);
+            return false;
+        };
+
+        let config = &self.config;
+        match due_at(config, self.date.and_time(check_in)) {
+            None => {
+                self.next_check = None;
+                false
+            }
+            Some(due) if now < due => {
+                self.next_check = Some(due);
+                false
+            }
+            Some(_) => {
+                let interval = config
+                    .repeat_minutes
+                    .max(1)
+                    .saturating_mul(1 << self.sent.min(16))
+                    .min(config.max_repeat_minutes.max(1));
+                self.sent += 1;
+                self.next_check = Some(now + ChronoDuration::minutes(interval as i64));
+                true
+            }
+        }
+    }
+}
+
+/// 출근 안내 종류
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckInPrompt {
+    /// 오늘 처음 컴퓨터를 씀
+    FirstActivity,
+    /// 코어타임 시작이 다가옴
+    CoreHours(NaiveTime),
+}
+
+/// 하루에 한 번씩 출근 안내와 코어타임 경고를 보낼지 정합니다.
+#[derive(Debug)]
+pub struct CheckInReminder {
+    date: NaiveDate,
+    config: CheckInReminderConfig,
+    prompted: bool,
+    warned: bool,
+    /// 출근 기록을 확인해 더 안내할 필요가 없음
+    checked_in: bool,
+}
+
+impl CheckInReminder {
+    pub fn new(date: NaiveDate, config: CheckInReminderConfig) -> Self {
+        let working_day = config.days.contains(&date.weekday());
+        Self {
+            date,
+            config,
+            prompted: false,
+            warned: false,
+            checked_in: !working_day,
+        }
+    }
+
+    fn warn_from(&self) -> Option<NaiveDateTime> {
+        self.config.core_start.map(|core| {
+            self.date.and_time(core)
+                - ChronoDuration::minutes(self.config.warn_minutes_before as i64)
+        })
+    }
+
+    /// 근태 상태를 조회해 볼 안내가 있으면 돌려줍니다. 자리에 있을 때만 안내합니다.
+    pub fn pending(&self, now: NaiveDateTime, active: bool) -> Option<CheckInPrompt> {
+        if self.checked_in || !active || now.time() < self.config.not_before {
+            return None;
+        }
+        if !self.prompted {
+            return Some(CheckInPrompt::FirstActivity);
+        }
+        let core = self.config.core_start?;
+        let approaching = self
+            .warn_from()
+            .is_some_and(|from| now >= from && now.time() < core);
+        (!self.warned && approaching).then_some(CheckInPrompt::CoreHours(core))
+    }
+
+    /// 조회한 상태를 반영하고, 출근 기록이 없어 안내해야 하면 `true` 를 돌려줍니다.
+    pub fn resolve(
+        &mut self,
+        prompt: CheckInPrompt,
+        now: NaiveDateTime,
+        status: &AttendanceStatus,
+    ) -> bool {
+        if status.check_in.is_some() {
+            self.checked_in = true;
+            return false;
+        }
+        match prompt {
+            CheckInPrompt::FirstActivity => {
+                self.prompted = true;
+                // 경고 구간에 처음 켰으면 곧바로 경고를 또 보내지 않음
+                self.warned = self.warn_from().is_some_and(|from| now >= from);
+            }
+            CheckInPrompt::CoreHours(_) => self.warned = true,
+        }
+        true
+    }
+}
+
+fn notify_check_in(app: &AppHandle, prompt: CheckInPrompt) {
+    let (title, body) = match prompt {
+        CheckInPrompt::FirstActivity => ("출근하셨나요?".to_string(), "오늘 출근 기록이 없습니다"),
+        CheckInPrompt::CoreHours(core) => (
+            format!("코어타임 {} 시작 전입니다", core.format("%H:%M")),
+            "아직 출근 기록이 없습니다",
+        ),
+    };
+    notify_with_button(app, &title, body, &CHECK_IN_BUTTON);
+}
+
+fn notify_check_out(app: &AppHandle, check_in: Option<NaiveTime>) {
+    let body = match check_in {
+        Some(t) => format!(
+            "{} 에 출근한 뒤 아직 퇴근 기록이 없습니다",
+            t.format("%H:%M")
+        ),
+        None => "아직 퇴근 기록이 없습니다".to_string(),
+    };
+    notify_with_button(app, "퇴근하셨나요?", &body, &CHECK_OUT_BUTTON);
+}
+
+/// 퇴근 기록이 없으면 퇴근을 반복해서 안내합니다.
+/// 퇴근이 기록되거나 날짜가 바뀌면 멈춥니다.
+async fn check_out_loop(app: AppHandle) {
+    let mut reminder: Option<CheckOutReminder> = None;
+
+    loop {
+        let now = Local::now().naive_local();
+        let state = app.state::<AppState>();
+        let config = state.config.get();
+        let settings = config.check_out_reminder.clone();
+
+        let stale = reminder
+            .as_ref()
+            .is_none_or(|r| r.date != now.date() || r.config != settings);
+        if stale {
+            reminder = None;
+        }
+
+        if settings.enabled && config.has_company_url() {
+            let day = reminder.get_or_insert_with(|| CheckOutReminder::new(now.date(), settings));
+
+            let day_off = DaysOff::load(&state.profile, &config.day_off).day_off(now.date());
+            let off = day_off.as_ref().is_some_and(DayOff::skips_check_out);
+            if !off && day.should_check(now) {
+                match state.worker.status().await {
+                    Ok(status) => {
+                        if day.observe(now, &status) {
+                            notify_check_out(&app, status.check_in);
+                        }
+                    }
+                    Err(e) => tracing::warn!(error = %e, "상태 조회 실패"),
+                }
+            }
+        }
+
+        tokio::time::sleep(TICK).await;
+    }
+}
+
+/// 세션 감지를 지원하지 않는 플랫폼에서는 앱이 돌고 있으면 사용 중으로 봅니다.
+fn session_active(session: Option<&Arc<dyn SessionBackend>>) -> bool {
+    let Some(session) = session else {
+        return true;
+    };
+    match session.state() {
+        Ok(state) => {
+            let idle = state
+                .idle_since
+                .and_then(|since| SystemTime::now().duration_since(since).ok())
+                .unwrap_or_default();
+            !state.locked && idle < ACTIVE_WITHIN
+        }
+        Err(e) => {
+            tracing::warn!("{}", e);
+            false
+        }
+    }
+}
+
+/// 출근 기록 없이 컴퓨터를 쓰기 시작하면 출근을, 코어타임이 다가오면 경고를 보냅니다.
+async fn check_in_loop(app: AppHandle, session: Option<Arc<dyn SessionBackend>>) {
+    let mut reminder: Option<CheckInReminder> = None;
+
+    loop {
+        let now = Local::now().naive_local();
+        let state = app.state::<AppState>();
+        let config = state.config.get();
+        let settings = config.check_in_reminder.clone();
+
+        let stale = reminder
+            .as_ref()
+            .is_none_or(|r| r.date != now.date() || r.config != settings);
+        if stale {
+            reminder = None;
+        }
+
+        if settings.enabled && config.has_company_url() {
+            let day = reminder.get_or_insert_with(|| CheckInReminder::new(now.date(), settings));
+
+            let day_off = DaysOff::load(&state.profile, &config.day_off).day_off(now.date());
+            let off = day_off.as_ref().is_some_and(DayOff::skips_check_in);
+            let session = session.clone();
+            let active =
+                tauri::async_runtime::spawn_blocking(move || session_active(session.as_ref()))
+                    .await
+                    .unwrap_or(false);
+
+            if let Some(prompt) = day.pending(now, active).filter(|_| !off) {
+                match state.worker.status().await {
+                    Ok(status) => {
+                        if day.resolve(prompt, now, &status) {
+                            notify_check_in(&app, prompt);
+                        }
+                    }
+                    Err(e) => tracing::warn!(error = %e, "상태 조회 실패"),
+                }
+            }
+        }
+
+        tokio::time::sleep(TICK).await;
+    }
+}
+
+/// 출퇴근 안내 백그라운드 태스크를 시작합니다.
+pub fn spawn(app: AppHandle, session: Option<Arc<dyn SessionBackend>>) {
+    tauri::async_runtime::spawn(check_out_loop(app.clone()));
+    tauri::async_runtime::spawn(check_in_loop(app, session));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()
+    }
+
+    fn status(check_in: Option<NaiveTime>, check_out: Option<NaiveTime>) -> AttendanceStatus {
+        AttendanceStatus {
+            check_in,
+            check_out,
+            status: None,
+        }
+    }
+
+    #[test]
+    fn due_is_the_later_of_both_rules() {
+        let mut config = CheckOutReminderConfig {
+            at: Some(time(18, 30)),
+            ..Default::default()
+        };
+        let early = date().and_time(time(8, 0));
+        let late = date().and_time(time(10, 0));
+        assert_eq!(due_at(&config, early), Some(date().and_time(time(18, 30))));
+        assert_eq!(due_at(&config, late), Some(date().and_time(time(19, 0))));
+
+        config.hours_after_check_in = None;
+        assert_eq!(due_at(&config, late), Some(date().and_time(time(18, 30))));
+        config.at = None;
+        assert_eq!(due_at(&config, late), None);
+    }
+
+    #[test]
+    fn repeats_with_backoff_until_checked_out() {
+        let mut reminder = CheckOutReminder::new(date(), CheckOutReminderConfig::default());
+        let working = status(Some(time(9, 0)), None);
+        let at = |h, m| date().and_time(time(h, m));
+
+        // 자정 + 9시간 전에는 조회하지 않음
+        assert!(!reminder.should_check(at(8, 59)));
+        assert!(reminder.should_check(at(9, 0)));
+        assert!(!reminder.observe(at(9, 0), &working));
+        assert!(!reminder.should_check(at(17, 59)));
+
+        let mut sent = Vec::new();
+        let mut now = at(18, 0);
+        while now < at(21, 0) {
+            if reminder.should_check(now) && reminder.observe(now, &working) {
+                sent.push(now.time().format("%H:%M").to_string());
+            }
+            now += ChronoDuration::minutes(1);
+        }
+        assert_eq!(sent, ["18:00", "18:15", "18:45", "19:45", "20:45"]);
+
+        let done = status(Some(time(9, 0)), Some(time(20, 50)));
+        assert!(!reminder.observe(at(21, 0), &done));
+        assert!(!reminder.should_check(at(23, 59)));
+    }
+
+    #[test]
+    fn retries_later_when_not_checked_in() {
+        let mut reminder = CheckOutReminder::new(date(), CheckOutReminderConfig::default());
+        let at = |h, m| date().and_time(time(h, m));
+
+        assert!(!reminder.observe(at(9, 0), &status(None, None)));
+        assert!(!reminder.should_check(at(9, 29)));
+        assert!(reminder.should_check(at(9, 30)));
+    }
+
+    #[test]
+    fn prompts_on_first_activity_then_warns_before_core_hours() {
+        let config = CheckInReminderConfig {
+            enabled: true,
+            core_start: Some(time(10, 0)),
+            ..Default::default()
+        };
+        let mut reminder = CheckInReminder::new(date(), config);
+        let none = status(None, None);
+        let at = |h, m| date().and_time(time(h, m));
+
+        assert_eq!(reminder.pending(at(5, 30), true), None);
+        assert_eq!(reminder.pending(at(9, 5), false), None);
+        assert_eq!(
+            reminder.pending(at(9, 5), true),
+            Some(CheckInPrompt::FirstActivity)
+        );
+        assert!(reminder.resolve(CheckInPrompt::FirstActivity, at(9, 5), &none));
+
+        assert_eq!(reminder.pending(at(9, 49), true), None);
+        let core = CheckInPrompt::CoreHours(time(10, 0));
+        assert_eq!(reminder.pending(at(9, 50), true), Some(core));
+        assert!(reminder.resolve(core, at(9, 50), &none));
+        assert_eq!(reminder.pending(at(9, 55), true), None);
+    }
+
+    #[test]
+    fn stops_once_checked_in_and_skips_other_days() {
+        let config = CheckInReminderConfig {
+            enabled: true,
+            core_start: Some(time(10, 0)),
+            ..Default::default()
+        };
+        let at = |h, m| date().and_time(time(h, m));
+
+        let mut reminder = CheckInReminder::new(date(), config.clone());
+        let prompt = reminder.pending(at(9, 0), true).unwrap();
+        assert!(!reminder.resolve(prompt, at(9, 0), &status(Some(time(8, 55)), None)));
+        assert_eq!(reminder.pending(at(9, 55), true), None);
+
+        // 경고 구간에 처음 켜면 출근 안내 하나만 보냄
+        let mut reminder = CheckInReminder::new(date(), config.clone());
+        assert!(reminder.resolve(CheckInPrompt::FirstActivity, at(9, 55), &status(None, None)));
+        assert_eq!(reminder.pending(at(9, 56), true), None);
+
+        let saturday = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap();
+        let reminder = CheckInReminder::new(saturday, config);
+        assert_eq!(reminder.pending(saturday.and_time(time(9, 0)), true), None);
+    }
+}
@@ -0,0 +1,449 @@
+use crate::config::ReportConfig;
+use crate::protocol::AttendanceDay;
+use crate::worker::WorkerHandle;
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReportPeriod {
+    Week,
+    Month,
+}
+
+/// 하루 근무 시간
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DayHours {
+    pub date: NaiveDate,
+    pub check_in: NaiveTime,
+    pub check_out: Option<NaiveTime>,
+    /// 아직 퇴근하지 않아 현재 시각까지로 계산함
+    pub in_progress: bool,
+    pub worked_minutes: i64,
+    pub overtime_minutes: i64,
+}
+
+/// 월요일부터 시작하는 한 주 (보고서 기간 밖의 날도 포함한 주 전체)
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WeekHours {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub worked_minutes: i64,
+    pub overtime_minutes: i64,
+    /// 주 최대 근로 시간까지 남은 시간
+    pub remaining_minutes: i64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkReport {
+    pub period: ReportPeriod,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub days: Vec<DayHours>,
+    pub weeks: Vec<WeekHours>,
+    pub worked_minutes: i64,
+    pub overtime_minutes: i64,
+}
+
+impl WorkReport {
+    /// 알림에 보여줄 한 줄 요약
+    pub fn summary(&self) -> String {
+        let mut parts = vec![
+            format!("근무 {}", format_minutes(self.worked_minutes)),
+            format!("연장 {}", format_minutes(self.overtime_minutes)),
+        ];
+        if let (ReportPeriod::Week, Some(week)) = (self.period, self.weeks.first()) {
+            parts.push(format!("남은 한도 {}", format_minutes(week.remaining_minutes)));
+        }
+        parts.join(" · ")
+    }
+}
+
+/// `date` 가 속한 주 (월-일) 또는 달의 첫날과 마지막 날
+pub fn period_range(period: ReportPeriod, date: NaiveDate) -> (NaiveDate, NaiveDate) {
+    match period {
+        ReportPeriod::Week => {
+            let from = date - Duration::days(i64::from(date.weekday().num_days_from_monday()));
+            (from, from + Duration::days(6))
+        }
+        ReportPeriod::Month => {
+            let from = date.with_day(1).unwrap_or(date);
+            let next = if from.month() == 12 {
+                NaiveDate::from_ymd_opt(from.year() + 1, 1, 1)
+            } else {
+                NaiveDate::from_ymd_opt(from.year(), from.month() + 1, 1)
+            };
+            (from, next.map_or(date, |d| d.pred_opt().unwrap_or(d)))
+        }
+    }
+}
+
+/// 기간에 걸친 주 전체, 첫 주의 월요일부터 마지막 주의 일요일까지
+fn covering_weeks(from: NaiveDate, to: NaiveDate) -> (NaiveDate, NaiveDate) {
+    (
+        period_range(ReportPeriod::Week, from).0,
+        period_range(ReportPeriod::Week, to).1,
+    )
+}
+
+/// 출퇴근 사이 시간에서 뺄 법정 휴게 시간 (분)
+///
+/// 근로 4시간에 30분, 8시간에 1시간이며, 휴게 시간을 빼면 기준에 못 미치는
+/// 구간에서는 기준 시간만큼만 일한 것으로 봅니다.
+fn break_minutes(span: i64) -> i64 {
+    match span {
+        s if s >= 9 * 60 => 60,
+        s if s >= 8 * 60 + 30 => s - 8 * 60,
+        s if s >= 4 * 60 + 30 => 30,
+        s if s >= 4 * 60 => s - 4 * 60,
+        _ => 0,
+    }
+}
+
+/// 출근 기록이 없는 날은 `None`
+pub fn day_hours(day: &AttendanceDay, now: NaiveDateTime, rules: &ReportConfig) -> Option<DayHours> {
+    let check_in = day.check_in?;
+    let start = day.date.and_time(check_in);
+
+    let (end, in_progress) = match day.check_out {
+        // 자정을 넘겨 퇴근한 경우
+        Some(out) if out < check_in => (day.date.and_time(out) + Duration::days(1), false),
+        Some(out) => (day.date.and_time(out), false),
+        None if day.date == now.date() => (now.max(start), true),
+        // 퇴근 기록이 빠진 지난 날은 계산하지 않음
+        None => (start, false),
+    };
+
+    let span = (end - start).num_minutes();
+    let worked = if rules.deduct_breaks {
+        span - break_minutes(span)
+    } else {
+        span
+    };
+    let standard = i64::from(rules.daily_standard_hours) * 60;
+
+    Some(DayHours {
+        date: day.date,
+        check_in,
+        check_out: day.check_out,
+        in_progress,
+        worked_minutes: worked,
+        overtime_minutes: (worked - standard).max(0),
+    })
+}
+
+/// 한 주의 연장 근로를 날짜별로 나눕니다.
+///
+/// 하루 기준을 넘긴 시간은 그날에, 하루 기준 안의 근무가 쌓여 주 기준을 넘긴 시간은
+/// 넘긴 날에 붙이므로 합은 하루 연장 근로의 합과 주 기준을 넘긴 시간 중 큰 값입니다.
+fn overtime_by_day(week: &[&DayHours], weekly_standard: i64) -> Vec<i64> {
+    let mut regular = 0;
+    week.iter()
+        .map(|d| {
+            let before = (regular - weekly_standard).max(0);
+            regular += d.worked_minutes - d.overtime_minutes;
+            d.overtime_minutes + (regular - weekly_standard).max(0) - before
+        })
+        .collect()
+}
+
+/// 날짜별 출퇴근 기록으로 기간 보고서를 만듭니다.
+///
+/// 주 연장 근로는 하루 기준을 넘긴 시간의 합과 주 기준을 넘긴 시간 중 큰 값입니다.
+/// 달의 첫 주와 마지막 주도 월-일 전체로 계산하므로 `records` 에는 앞뒤 주의 기록도
+/// 있어야 하고, 날짜별 내역과 기간 합계에는 기간 안의 날만 들어갑니다.
+pub fn build(
+    period: ReportPeriod,
+    date: NaiveDate,
+    records: &[AttendanceDay],
+    now: NaiveDateTime,
+    rules: &ReportConfig,
+) -> WorkReport {
+    let (from, to) = period_range(period, date);
+    let (first_monday, last_sunday) = covering_weeks(from, to);
+    let in_period = |d: NaiveDate| from <= d && d <= to;
+
+    let mut days: Vec<DayHours> = records
+        .iter()
+        .filter(|d| first_monday <= d.date && d.date <= last_sunday)
+        .filter_map(|d| day_hours(d, now, rules))
+        .collect();
+    days.sort_by_key(|d| d.date);
+    days.dedup_by_key(|d| d.date);
+
+    let weekly_standard = i64::from(rules.weekly_standard_hours) * 60;
+    let weekly_limit = i64::from(rules.weekly_limit_hours) * 60;
+
+    let mut weeks = Vec::new();
+    let mut overtime = 0;
+    let mut week_start = first_monday;
+    while week_start <= last_sunday {
+        let week_end = week_start + Duration::days(6);
+
+        let in_week: Vec<&DayHours> = days
+            .iter()
+            .filter(|d| week_start <= d.date && d.date <= week_end)
+            .collect();
+        let worked: i64 = in_week.iter().map(|d| d.worked_minutes).sum();
+        let by_day = overtime_by_day(&in_week, weekly_standard);
+        overtime += in_week
+            .iter()
+            .zip(&by_day)
+            .filter(|(d, _)| in_period(d.date))
+            .map(|(_, minutes)| minutes)
+            .sum::<i64>();
+
+        weeks.push(WeekHours {
+            from: week_start,
+            to: week_end,
+            worked_minutes: worked,
+            overtime_minutes: by_day.iter().sum(),
+            remaining_minutes: (weekly_limit - worked).max(0),
+        });
+        week_start = week_end + Duration::days(1);
+    }
+
+    days.retain(|d| in_period(d.date));
+    WorkReport {
+        period,
+        from,
+        to,
+        worked_minutes: days.iter().map(|d| d.worked_minutes).sum(),
+        overtime_minutes: overtime,
+        days,
+        weeks,
+    }
+}
+
+/// 근무 기록 페이지와 오늘의 `getStatus` 결과로 보고서를 만듭니다.
+pub async fn fetch(
+    worker: &WorkerHandle,
+    rules: &ReportConfig,
+    period: ReportPeriod,
+    date: NaiveDate,
+) -> Result<WorkReport, String> {
+    let (from, to) = period_range(period, date);
+    // 달에 걸친 첫 주와 마지막 주는 앞뒤 달의 기록까지 읽어야 주 합계가 맞음
+    let (from, to) = covering_weeks(from, to);
+    let now = Local::now().naive_local();
+
+    let mut records = worker
+        .attendance_history(from, to)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // 기록 페이지는 오늘 출퇴근이 늦게 반영될 수 있어 현재 상태로 덮어씀
+    let today = now.date();
+    if from <= today && today <= to {
+        let status = worker.status().await.map_err(|e| e.to_string())?;
+        records.retain(|d| d.date != today);
+        records.push(AttendanceDay {
+            date: today,
+            check_in: status.check_in,
+            check_out: status.check_out,
+        });
+    }
+
+    Ok(build(period, date, &records, now, rules))
+}
+
+/// `125` → `2시간 5분`
+pub fn format_minutes(minutes: i64) -> String {
+    match (minutes / 60, minutes % 60) {
+        (0, m) => format!("{}분", m),
+        (h, 0) => format!("{}시간", h),
+        (h, m) => format!("{}시간 {}분", h, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn record(d: u32, check_in: (u32, u32), check_out: Option<(u32, u32)>) -> AttendanceDay {
+        AttendanceDay {
+            date: date(d),
+            check_in: Some(time(check_in.0, check_in.1)),
+            check_out: check_out.map(|(h, m)| time(h, m)),
+        }
+    }
+
+    fn noon(d: u32) -> NaiveDateTime {
+        date(d).and_time(time(12, 0))
+    }
+
+    #[test]
+    fn week_and_month_ranges() {
+        // 2026-10-07 은 수요일
+        assert_eq!(period_range(ReportPeriod::Week, date(7)), (date(5), date(11)));
+        assert_eq!(period_range(ReportPeriod::Month, date(7)), (date(1), date(31)));
+
+        let dec = NaiveDate::from_ymd_opt(2026, 12, 15).unwrap();
+        assert_eq!(
+            period_range(ReportPeriod::Month, dec).1,
+            NaiveDate::from_ymd_opt(2026, 12, 31).unwrap()
+        );
+    }
+
+    #[test]
+    fn deducts_statutory_breaks() {
+        let rules = ReportConfig::default();
+        let hours = |out| day_hours(&record(5, (9, 0), Some(out)), noon(20), &rules).unwrap();
+
+        assert_eq!(hours((12, 0)).worked_minutes, 3 * 60);
+        assert_eq!(hours((13, 15)).worked_minutes, 4 * 60);
+        assert_eq!(hours((14, 0)).worked_minutes, 4 * 60 + 30);
+        assert_eq!(hours((17, 45)).worked_minutes, 8 * 60);
+        assert_eq!(hours((18, 0)).worked_minutes, 8 * 60);
+        assert_eq!(hours((20, 0)).worked_minutes, 10 * 60);
+        assert_eq!(hours((20, 0)).overtime_minutes, 2 * 60);
+    }
+
+    #[test]
+    fn breaks_can_be_disabled() {
+        let rules = ReportConfig {
+            deduct_breaks: false,
+            ..ReportConfig::default()
+        };
+        let day = day_hours(&record(5, (9, 0), Some((18, 0))), noon(20), &rules).unwrap();
+        assert_eq!(day.worked_minutes, 9 * 60);
+    }
+
+    #[test]
+    fn checkout_after_midnight() {
+        let day = day_hours(
+            &record(5, (9, 0), Some((1, 0))),
+            noon(20),
+            &ReportConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(day.worked_minutes, 15 * 60);
+    }
+
+    #[test]
+    fn today_counts_until_now() {
+        let day = day_hours(&record(7, (8, 0), None), noon(7), &ReportConfig::default()).unwrap();
+        assert!(day.in_progress);
+        assert_eq!(day.worked_minutes, 4 * 60);
+
+        // 지난 날 퇴근 기록이 없으면 0
+        let past = day_hours(&record(6, (8, 0), None), noon(7), &ReportConfig::default()).unwrap();
+        assert!(!past.in_progress);
+        assert_eq!(past.worked_minutes, 0);
+    }
+
+    #[test]
+    fn weekly_overtime_and_remaining() {
+        // 월-금 09:00-20:00 (하루 10시간) = 50시간
+        let records: Vec<_> = (5..=9).map(|d| record(d, (9, 0), Some((20, 0)))).collect();
+        let report = build(
+            ReportPeriod::Week,
+            date(7),
+            &records,
+            noon(12),
+            &ReportConfig::default(),
+        );
+
+        assert_eq!(report.days.len(), 5);
+        assert_eq!(report.worked_minutes, 50 * 60);
+        assert_eq!(report.overtime_minutes, 10 * 60);
+        assert_eq!(report.weeks.len(), 1);
+        assert_eq!(report.weeks[0].remaining_minutes, 2 * 60);
+        assert_eq!(
+            report.summary(),
+            "근무 50시간 · 연장 10시간 · 남은 한도 2시간"
+        );
+    }
+
+    #[test]
+    fn weekly_standard_overtime_without_daily_overtime() {
+        // 월-토 하루 8시간 = 48시간, 하루 기준은 넘지 않지만 주 40시간 초과
+        let records: Vec<_> = (5..=10).map(|d| record(d, (9, 0), Some((18, 0)))).collect();
+        let report = build(
+            ReportPeriod::Week,
+            date(5),
+            &records,
+            noon(12),
+            &ReportConfig::default(),
+        );
+        assert_eq!(report.overtime_minutes, 8 * 60);
+    }
+
+    #[test]
+    fn month_is_split_into_weeks() {
+        let records = vec![
+            record(1, (9, 0), Some((18, 0))),
+            record(30, (9, 0), Some((18, 0))),
+            record(31, (9, 0), Some((18, 0))),
+        ];
+        let report = build(
+            ReportPeriod::Month,
+            date(15),
+            &records,
+            noon(31),
+            &ReportConfig::default(),
+        );
+
+        // 9/28-10/4, 10/5-11, 12-18, 19-25, 10/26-11/1
+        assert_eq!(report.weeks.len(), 5);
+        assert_eq!(
+            report.weeks[0].from,
+            NaiveDate::from_ymd_opt(2026, 9, 28).unwrap()
+        );
+        assert_eq!(report.weeks[0].to, date(4));
+        assert_eq!(
+            report.weeks[4].to,
+            NaiveDate::from_ymd_opt(2026, 11, 1).unwrap()
+        );
+        assert_eq!(report.worked_minutes, 3 * 8 * 60);
+    }
+
+    #[test]
+    fn weeks_crossing_the_month_are_counted_whole() {
+        // 9/28(월)-10/3(토) 하루 8시간 = 48시간, 주 40시간은 10/2 에 채움
+        let september = |d| AttendanceDay {
+            date: NaiveDate::from_ymd_opt(2026, 9, d).unwrap(),
+            check_in: Some(time(9, 0)),
+            check_out: Some(time(18, 0)),
+        };
+        let mut records: Vec<_> = (28..=30).map(september).collect();
+        records.extend((1..=3).map(|d| record(d, (9, 0), Some((18, 0)))));
+
+        let report = build(
+            ReportPeriod::Month,
+            date(15),
+            &records,
+            noon(31),
+            &ReportConfig::default(),
+        );
+
+        let first = &report.weeks[0];
+        assert_eq!(first.worked_minutes, 48 * 60);
+        assert_eq!(first.overtime_minutes, 8 * 60);
+        assert_eq!(first.remaining_minutes, 4 * 60);
+
+        // 날짜별 내역과 달 합계는 10월만
+        assert_eq!(report.days.len(), 3);
+        assert_eq!(report.worked_minutes, 24 * 60);
+        assert_eq!(report.overtime_minutes, 8 * 60);
+    }
+
+    #[test]
+    fn formats_minutes() {
+        assert_eq!(format_minutes(0), "0분");
+        assert_eq!(format_minutes(45), "45분");
+        assert_eq!(format_minutes(120), "2시간");
+        assert_eq!(format_minutes(125), "2시간 5분");
+    }
+}
@@ -0,0 +1,354 @@
+use crate::config::ScheduleConfig;
+use crate::holidays::{DayOff, DaysOff};
+use crate::journal::Journal;
+use crate::profile::Profile;
+use crate::{run_worker_action, AppState, WorkerAction, CHECK_IN, CHECK_OUT};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::{AppHandle, Manager};
+
+/// 예약 시각을 확인하는 간격
+const TICK: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// 예약 시각을 이만큼 넘기면 (잠자기 등) 실행하지 않고 건너뜀
+const MISSED_GRACE: Duration = Duration::minutes(30);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScheduledAction {
+    CheckIn,
+    CheckOut,
+}
+
+impl ScheduledAction {
+    fn worker_action(self) -> &'static WorkerAction {
+        match self {
+            ScheduledAction::CheckIn => &CHECK_IN,
+            ScheduledAction::CheckOut => &CHECK_OUT,
+        }
+    }
+}
+
+/// `schedule-log.jsonl` 에 남기는 실행 기록
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleLogEntry {
+    pub fired_at: DateTime<Local>,
+    pub scheduled_for: NaiveDateTime,
+    pub action: String,
+    pub success: bool,
+    pub message: String,
+}
+
+pub fn log_journal(profile: &Profile) -> Journal {
+    Journal::new(profile.dir().join("schedule-log.jsonl"))
+}
+
+/// 하루치 예약 시각을 계산합니다. 지터는 날마다 한 번만 뽑습니다.
+/// 공휴일/휴가면 건너뛰고, 반차면 쉬는 쪽 예약만 뺍니다.
+pub fn plan_for(
+    date: NaiveDate,
+    schedule: &ScheduleConfig,
+    day_off: Option<&DayOff>,
+    rng: &mut impl Rng,
+) -> Vec<(ScheduledAction, NaiveDateTime)> {
+    if !schedule.enabled || schedule.holidays.contains(&date) {
+        return Vec::new();
+    }
+
+    let weekday = date.weekday();
+    let jitter = i64::from(schedule.jitter_minutes);
+
+    [
+        (ScheduledAction::CheckIn, schedule.check_in.get(&weekday)),
+        (ScheduledAction::CheckOut, schedule.check_out.get(&weekday)),
+    ]
+    .into_iter()
+    .filter(|(action, _)| match (day_off, action) {
+        (Some(off), ScheduledAction::CheckIn) => !off.skips_check_in(),
+        (Some(off), ScheduledAction::CheckOut) => !off.skips_check_out(),
+        (None, _) => true,
+    })
+    .filter_map(|(action, time)| {
+        let offset = if jitter > 0 {
+            rng.gen_range(-jitter..=jitter)
+        } else {
+            0
+        };
+        time.map(|t| (action, date.and_time(*t) + Duration::minutes(offset)))
+    })
+    .collect()
+}
+
+/// 예약 시각이 지난 항목
+#[derive(Debug, PartialEq)]
+enum Due {
+    Run(ScheduledAction, NaiveDateTime),
+    /// 잠자기 등으로 `MISSED_GRACE` 를 넘겨 실행하지 않음
+    Missed(ScheduledAction, NaiveDateTime),
+}
+
+struct DayPlan {
+    date: NaiveDate,
+    schedule: ScheduleConfig,
+    day_off: Option<DayOff>,
+    entries: Vec<(ScheduledAction, NaiveDateTime)>,
+    fired: HashSet<ScheduledAction>,
+}
+
+impl DayPlan {
+    fn new(
+        date: NaiveDate,
+        schedule: ScheduleConfig,
+        day_off: Option<DayOff>,
+        journal: &Journal,
+    ) -> Self {
+        let entries = plan_for(date, &schedule, day_off.as_ref(), &mut rand::thread_rng());
+        if let Some(off) = &day_off {
+            tracing::info!("{} ({}) 예약을 건너뜁니다", date, off.describe());
+        }
+
+        // 앱을 다시 켜도 오늘 이미 실행한 예약은 반복하지 않음
+        let fired = journal
+            .read::<ScheduleLogEntry>()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|e| e.scheduled_for.date() == date)
+            .filter_map(|e| match e.action.as_str() {
+                "checkIn" => Some(ScheduledAction::CheckIn),
+                "checkOut" => Some(ScheduledAction::CheckOut),
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            date,
+            schedule,
+            day_off,
+            entries,
+            fired,
+        }
+    }
+
+    /// `now` 까지 시각이 된 예약을 꺼냅니다. 한 번 꺼낸 예약은 다시 나오지 않습니다.
+    fn take_due(&mut self, now: NaiveDateTime) -> Vec<Due> {
+        let mut due = Vec::new();
+        for &(action, at) in &self.entries {
+            if now < at || !self.fired.insert(action) {
+                continue;
+            }
+            due.push(if now - at > MISSED_GRACE {
+                Due::Missed(action, at)
+            } else {
+                Due::Run(action, at)
+            });
+        }
+        due
+    }
+}
+
+/// 설정된 시각에 출퇴근을 실행하는 백그라운드 태스크를 시작합니다.
+pub fn spawn(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let journal = log_journal(&app.state::<AppState>().profile);
+        let mut plan: Option<DayPlan> = None;
+
+        loop {
+            let now = Local::now().naive_local();
+            let state = app.state::<AppState>();
+            let config = state.config.get();
+            let schedule = config.schedule;
+            // 휴가 내역은 중간에 새로 읽힐 수 있어 매번 확인
+            let day_off = DaysOff::load(&state.profile, &config.day_off).day_off(now.date());
+
+            let stale = plan.as_ref().is_none_or(|p| {
+                p.date != now.date() || p.schedule != schedule || p.day_off != day_off
+            });
+            if stale {
+                plan = None;
+            }
+            let day = plan
+                .get_or_insert_with(|| DayPlan::new(now.date(), schedule, day_off, &journal));
+
+            for due in day.take_due(now) {
+                let (action, at) = match due {
+                    Due::Run(action, at) => (action, at),
+                    Due::Missed(action, at) => {
+                        tracing::info!(
+                            "{} 예약 시각({})을 지나 건너뜁니다",
+                            action.worker_action().request.action(),
+                            at
+                        );
+                        continue;
+                    }
+                };
+
+                let worker_action = action.worker_action();
+
+                let result = run_worker_action(&app, worker_action).await;
+                let entry = ScheduleLogEntry {
+                    fired_at: Local::now(),
+                    scheduled_for: at,
+                    action: worker_action.request.action().to_string(),
+                    success: result.is_ok(),
+                    message: result.unwrap_or_else(|e| e),
+                };
+
+                if let Err(e) = journal.append(&entry) {
+                    tracing::warn!(error = %e, "실행 기록 저장 실패");
+                }
+            }
+
+            tokio::time::sleep(TICK).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{LeaveDay, LeavePeriod};
+    use chrono::{NaiveTime, Weekday};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// 2026-10-12 는 월요일
+    fn monday() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()
+    }
+
+    fn at(date: NaiveDate, hour: u32, minute: u32) -> NaiveDateTime {
+        date.and_time(NaiveTime::from_hms_opt(hour, minute, 0).unwrap())
+    }
+
+    fn schedule(jitter_minutes: u32) -> ScheduleConfig {
+        ScheduleConfig {
+            enabled: true,
+            check_in: [(Weekday::Mon, NaiveTime::from_hms_opt(9, 0, 0).unwrap())].into(),
+            check_out: [(Weekday::Mon, NaiveTime::from_hms_opt(18, 0, 0).unwrap())].into(),
+            jitter_minutes,
+            holidays: Vec::new(),
+        }
+    }
+
+    fn leave(period: LeavePeriod) -> DayOff {
+        DayOff::Leave(LeaveDay {
+            date: monday(),
+            kind: "연차".to_string(),
+            period,
+        })
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        let schedule = schedule(10);
+        let mut offsets = HashSet::new();
+        for seed in 0..200 {
+            let plan = plan_for(monday(), &schedule, None, &mut StdRng::seed_from_u64(seed));
+            assert_eq!(plan.len(), 2);
+            for (action, time) in plan {
+                let base = match action {
+                    ScheduledAction::CheckIn => at(monday(), 9, 0),
+                    ScheduledAction::CheckOut => at(monday(), 18, 0),
+                };
+                let offset = (time - base).num_minutes();
+                assert!((-10..=10).contains(&offset), "{}", offset);
+                offsets.insert(offset);
+            }
+        }
+        // 양 끝을 포함해 고르게 흔들림
+        assert!(offsets.contains(&-10) && offsets.contains(&10));
+
+        // 같은 시드는 같은 예약
+        let plan = |seed| plan_for(monday(), &schedule, None, &mut StdRng::seed_from_u64(seed));
+        assert_eq!(plan(7), plan(7));
+    }
+
+    #[test]
+    fn no_jitter_keeps_exact_times() {
+        let plan = plan_for(monday(), &schedule(0), None, &mut StdRng::seed_from_u64(1));
+        assert_eq!(
+            plan,
+            [
+                (ScheduledAction::CheckIn, at(monday(), 9, 0)),
+                (ScheduledAction::CheckOut, at(monday(), 18, 0)),
+            ]
+        );
+        // 예약이 없는 요일
+        let tuesday = monday().succ_opt().unwrap();
+        assert!(plan_for(tuesday, &schedule(0), None, &mut StdRng::seed_from_u64(1)).is_empty());
+    }
+
+    #[test]
+    fn holidays_and_days_off_skip_the_day() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let mut with_holiday = schedule(0);
+        with_holiday.holidays.push(monday());
+        assert!(plan_for(monday(), &with_holiday, None, &mut rng).is_empty());
+
+        let holiday = DayOff::Holiday {
+            name: "한글날".to_string(),
+        };
+        assert!(plan_for(monday(), &schedule(0), Some(&holiday), &mut rng).is_empty());
+        assert!(plan_for(
+            monday(),
+            &schedule(0),
+            Some(&leave(LeavePeriod::Full)),
+            &mut rng
+        )
+        .is_empty());
+
+        let mut disabled = schedule(0);
+        disabled.enabled = false;
+        assert!(plan_for(monday(), &disabled, None, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn half_day_leave_keeps_the_working_half() {
+        let mut rng = StdRng::seed_from_u64(1);
+
+        // 오전 반차: 출근은 직접, 퇴근 예약만 남음
+        let morning = plan_for(
+            monday(),
+            &schedule(0),
+            Some(&leave(LeavePeriod::Morning)),
+            &mut rng,
+        );
+        assert_eq!(morning, [(ScheduledAction::CheckOut, at(monday(), 18, 0))]);
+
+        let afternoon = plan_for(
+            monday(),
+            &schedule(0),
+            Some(&leave(LeavePeriod::Afternoon)),
+            &mut rng,
+        );
+        assert_eq!(afternoon, [(ScheduledAction::CheckIn, at(monday(), 9, 0))]);
+    }
+
+    #[test]
+    fn missed_schedule_after_wake_is_skipped() {
+        let mut plan = DayPlan {
+            date: monday(),
+            schedule: schedule(0),
+            day_off: None,
+            entries: plan_for(monday(), &schedule(0), None, &mut StdRng::seed_from_u64(1)),
+            fired: HashSet::new(),
+        };
+
+        assert!(plan.take_due(at(monday(), 8, 59)).is_empty());
+        // 유예 시간 안에 깨어나면 실행, 한 번만
+        assert_eq!(
+            plan.take_due(at(monday(), 9, 30)),
+            [Due::Run(ScheduledAction::CheckIn, at(monday(), 9, 0))]
+        );
+        assert!(plan.take_due(at(monday(), 9, 31)).is_empty());
+        // 퇴근 예약을 30분 넘겨 깨어나면 건너뜀
+        assert_eq!(
+            plan.take_due(at(monday(), 18, 31)),
+            [Due::Missed(ScheduledAction::CheckOut, at(monday(), 18, 0))]
+        );
+        assert!(plan.take_due(at(monday(), 19, 0)).is_empty());
+    }
+}
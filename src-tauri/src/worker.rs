@@ -0,0 +1,352 @@
+use crate::config::{Config, ConfigStore};
+use crate::credentials::{CredentialStore, DEFAULT_ACCOUNT};
+use crate::history::HistoryEntry;
+use crate::journal::Journal;
+use crate::playwright::{PlaywrightError, PlaywrightWorker};
+use crate::protocol::{ActionReply, AttendanceDay, AttendanceStatus, LeaveDay, WorkerRequest};
+use chrono::{Local, NaiveDate};
+use serde::de::DeserializeOwned;
+use serde_json::json;
+use std::collections::HashMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+type Reply = oneshot::Sender<Result<serde_json::Value, PlaywrightError>>;
+
+enum Job {
+    Command {
+        request: WorkerRequest,
+        reply: Reply,
+    },
+    Stop {
+        reply: oneshot::Sender<()>,
+    },
+}
+
+/// 액터 스레드가 명령을 보내는 대상, 테스트에서는 가짜 워커로 바꿉니다.
+trait Backend {
+    fn send_command(
+        &mut self,
+        action: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, PlaywrightError>;
+
+    fn stop(&mut self);
+}
+
+impl Backend for PlaywrightWorker {
+    fn send_command(
+        &mut self,
+        action: &str,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value, PlaywrightError> {
+        PlaywrightWorker::send_command(self, action, params)
+    }
+
+    fn stop(&mut self) {
+        let _ = PlaywrightWorker::stop(self);
+    }
+}
+
+/// `PlaywrightWorker` 를 소유한 전용 스레드에 명령을 보내는 핸들입니다.
+///
+/// 브라우저 명령은 큐에 쌓여 하나씩 처리되고, 호출하는 async 태스크는
+/// 응답을 기다리는 동안 런타임 스레드를 막지 않습니다.
+/// 워커는 설정을 저장하지 않으며, 회사 URL/아이디/비밀번호는
+/// 로그인이 필요한 명령마다 파라미터로 넘깁니다.
+pub struct WorkerHandle {
+    tx: mpsc::UnboundedSender<Job>,
+    history: Arc<Journal>,
+}
+
+impl WorkerHandle {
+    pub fn spawn(
+        config: Arc<ConfigStore>,
+        credentials: Arc<dyn CredentialStore>,
+        history: Arc<Journal>,
+        browser_data: PathBuf,
+        capture_dir: PathBuf,
+    ) -> Self {
+        let timeouts: HashMap<String, Duration> = config
+            .get()
+            .timeouts
+            .into_iter()
+            .map(|(action, secs)| (action, Duration::from_secs(secs)))
+            .collect();
+
+        let new_worker = move || {
+            let mut worker = PlaywrightWorker::new();
+            worker.set_browser_data(browser_data.clone());
+            worker.set_capture_dir(capture_dir.clone());
+            for (action, timeout) in &timeouts {
+                worker.set_timeout(action, *timeout);
+            }
+            worker
+        };
+        Self::with_backend(config, credentials, history, new_worker)
+    }
+
+    fn with_backend<B, F>(
+        config: Arc<ConfigStore>,
+        credentials: Arc<dyn CredentialStore>,
+        history: Arc<Journal>,
+        new_worker: F,
+    ) -> Self
+    where
+        B: Backend,
+        F: Fn() -> B + Send + 'static,
+    {
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        thread::Builder::new()
+            .name("playwright-worker".to_string())
+            .spawn(move || run_actor(rx, config, credentials, new_worker))
+            .expect("워커 스레드를 시작할 수 없습니다");
+
+        Self { tx, history }
+    }
+
+    /// 출퇴근, 근무 상태 변경, 로그인 창 열기처럼 메시지를 돌려주는 요청
+    ///
+    /// 로그인 창 열기를 제외한 요청은 결과와 함께 출퇴근 기록에 남깁니다.
+    pub async fn run(&self, request: WorkerRequest) -> Result<ActionReply, PlaywrightError> {
+        let requested_at = Local::now();
+        let result = self.request::<ActionReply>(request).await;
+
+        if request != WorkerRequest::OpenLogin {
+            let entry = HistoryEntry::from_result(requested_at, request, &result)
+                .with_capture(result.as_ref().err().and_then(PlaywrightError::capture));
+            if let Err(e) = self.history.append(&entry) {
+                tracing::warn!(error = %e, "출퇴근 기록 저장 실패");
+            }
+        }
+
+        result
+    }
+
+    pub async fn status(&self) -> Result<AttendanceStatus, PlaywrightError> {
+        self.request(WorkerRequest::GetStatus).await
+    }
+
+    pub async fn attendance_history(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<AttendanceDay>, PlaywrightError> {
+        self.request(WorkerRequest::GetAttendanceHistory { from, to })
+            .await
+    }
+
+    pub async fn approved_leave(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<Vec<LeaveDay>, PlaywrightError> {
+        self.request(WorkerRequest::GetApprovedLeave { from, to })
+            .await
+    }
+
+    pub async fn is_logged_in(&self) -> Result<bool, PlaywrightError> {
+        self.request(WorkerRequest::IsLoggedIn).await
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        request: WorkerRequest,
+    ) -> Result<T, PlaywrightError> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(Job::Command { request, reply })
+            .map_err(|_| PlaywrightError::NotRunning)?;
+        let value = rx.await.map_err(|_| PlaywrightError::NotRunning)??;
+
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// 진행 중인 명령이 끝난 뒤 워커 프로세스를 종료합니다.
+    pub async fn stop(&self) {
+        let (reply, rx) = oneshot::channel();
+        if self.tx.send(Job::Stop { reply }).is_ok() {
+            let _ = rx.await;
+        }
+    }
+}
+
+/// 요청 파라미터에 로그인에 필요한 회사 URL/아이디/비밀번호를 더합니다.
+pub(crate) fn request_params(
+    request: WorkerRequest,
+    config: &Config,
+    credentials: &dyn CredentialStore,
+) -> Result<serde_json::Value, PlaywrightError> {
+    let mut params = request.params();
+    if request.needs_login() {
+        let password = credentials
+            .get(DEFAULT_ACCOUNT)
+            .map_err(|e| PlaywrightError::ResponseError(e.to_string()))?;
+        if let Some(object) = params.as_object_mut() {
+            object.insert("companyUrl".to_string(), config.company_url.clone().into());
+            object.insert("username".to_string(), config.username.clone().into());
+            object.insert("password".to_string(), password.into());
+            // 로그인이 필요한 요청만 브라우저 화면이 있음
+            if config.capture.enabled {
+                object.insert(
+                    "capture".to_string(),
+                    json!({ "keep": config.capture.keep }),
+                );
+            }
+        }
+    }
+    Ok(params)
+}
+
+/// 큐에 쌓인 명령을 하나씩 처리합니다.
+///
+/// 키체인 조회가 async 런타임 스레드를 막지 않도록 파라미터도 이 스레드에서 만듭니다.
+fn run_actor<B: Backend>(
+    mut rx: mpsc::UnboundedReceiver<Job>,
+    config: Arc<ConfigStore>,
+    credentials: Arc<dyn CredentialStore>,
+    new_worker: impl Fn() -> B,
+) {
+    let mut worker = new_worker();
+
+    while let Some(job) = rx.blocking_recv() {
+        match job {
+            Job::Command { request, reply } => {
+                let action = request.action();
+                let result = match request_params(request, &config.get(), credentials.as_ref()) {
+                    Ok(params) => {
+                        match panic::catch_unwind(AssertUnwindSafe(|| {
+                            worker.send_command(action, params)
+                        })) {
+                            Ok(result) => result,
+                            Err(_) => {
+                                // 상태를 알 수 없으므로 프로세스를 버리고 새 워커로 교체
+                                tracing::error!("{} 처리 중 패닉, 워커를 교체합니다", action);
+                                worker.stop();
+                                worker = new_worker();
+                                Err(PlaywrightError::ResponseError(
+                                    "워커 내부 오류가 발생했습니다".to_string(),
+                                ))
+                            }
+                        }
+                    }
+                    Err(e) => Err(e),
+                };
+
+                let _ = reply.send(result);
+            }
+            Job::Stop { reply } => {
+                worker.stop();
+                let _ = reply.send(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::credentials::MemoryStore;
+    use crate::protocol::WorkStatus;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    /// 받은 명령을 기록하고, `checkOut` 을 받으면 패닉하는 가짜 워커
+    struct FakeWorker {
+        id: usize,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Backend for FakeWorker {
+        fn send_command(
+            &mut self,
+            action: &str,
+            _params: serde_json::Value,
+        ) -> Result<serde_json::Value, PlaywrightError> {
+            if action == "checkOut" {
+                panic!("가짜 워커 패닉");
+            }
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{}:{}", self.id, action));
+            Ok(json!(action))
+        }
+
+        fn stop(&mut self) {}
+    }
+
+    fn fake_handle(name: &str) -> (WorkerHandle, Arc<Mutex<Vec<String>>>) {
+        let dir =
+            std::env::temp_dir().join(format!("hiworks-actor-{}-{}", name, std::process::id()));
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let worker_log = log.clone();
+        let created = AtomicUsize::new(0);
+        let handle = WorkerHandle::with_backend(
+            Arc::new(ConfigStore::empty(dir.join("config.json"))),
+            Arc::new(MemoryStore::default()),
+            Arc::new(Journal::new(dir.join("history.jsonl"))),
+            move || FakeWorker {
+                id: created.fetch_add(1, Ordering::SeqCst),
+                log: worker_log.clone(),
+            },
+        );
+        (handle, log)
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn queued_commands_run_in_order() {
+        let (handle, log) = fake_handle("order");
+
+        let (check_in, status, meeting) = block_on(async {
+            tokio::join!(
+                handle.request::<String>(WorkerRequest::CheckIn),
+                handle.request::<String>(WorkerRequest::GetStatus),
+                handle.request::<String>(WorkerRequest::SetStatus(WorkStatus::Meeting)),
+            )
+        });
+
+        // 응답이 각 요청에 돌아감
+        assert_eq!(check_in.unwrap(), "checkIn");
+        assert_eq!(status.unwrap(), "getStatus");
+        assert_eq!(meeting.unwrap(), "setMeeting");
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["0:checkIn", "0:getStatus", "0:setMeeting"]
+        );
+    }
+
+    #[test]
+    fn panic_replaces_worker() {
+        let (handle, log) = fake_handle("panic");
+
+        block_on(async {
+            assert!(matches!(
+                handle.request::<String>(WorkerRequest::CheckOut).await,
+                Err(PlaywrightError::ResponseError(_))
+            ));
+            // 액터 스레드는 살아 있고 새 워커가 다음 요청을 처리
+            assert_eq!(
+                handle
+                    .request::<String>(WorkerRequest::GetStatus)
+                    .await
+                    .unwrap(),
+                "getStatus"
+            );
+        });
+        assert_eq!(*log.lock().unwrap(), ["1:getStatus"]);
+    }
+}
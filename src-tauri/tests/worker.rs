@@ -0,0 +1,264 @@
+//! `scripts/mock-worker.js` 로 Chromium 없이 워커 프로토콜을 검증합니다.
+
+use hiworks_commute_lib::playwright::{PlaywrightError, PlaywrightWorker, WorkerLaunch};
+use hiworks_commute_lib::protocol::{
+    ActionReply, AttendanceDay, AttendanceStatus, LeaveDay, LeavePeriod,
+};
+use serde_json::json;
+use std::path::PathBuf;
+use std::time::Duration;
+
+fn mock_launch() -> WorkerLaunch {
+    let script = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../scripts/mock-worker.js");
+    WorkerLaunch::new("node", script)
+}
+
+fn mock_worker() -> PlaywrightWorker {
+    PlaywrightWorker::with_launch(mock_launch())
+}
+
+/// 테스트마다 따로 쓰는 임시 디렉토리
+fn state_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "hiworks-mock-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn check_in_then_status() {
+    let mut worker = mock_worker();
+
+    let reply: ActionReply =
+        serde_json::from_value(worker.send_command("checkIn", json!({})).unwrap()).unwrap();
+    assert!(reply.message.starts_with("출근 완료"));
+    assert!(reply.check_time.is_some());
+
+    let status: AttendanceStatus =
+        serde_json::from_value(worker.send_command("getStatus", json!({})).unwrap()).unwrap();
+    assert!(status.check_in.is_some());
+    assert!(status.check_out.is_none());
+    assert_eq!(status.status.as_deref(), Some("업무"));
+}
+
+#[test]
+fn attendance_history_weekdays() {
+    let mut worker = mock_worker();
+    let value = worker
+        .send_command(
+            "getAttendanceHistory",
+            json!({ "from": "2026-10-05", "to": "2026-10-11" }),
+        )
+        .unwrap();
+
+    let days: Vec<AttendanceDay> = serde_json::from_value(value).unwrap();
+    assert_eq!(days.len(), 5);
+    assert!(days.iter().all(|d| d.check_in.is_some() && d.check_out.is_some()));
+}
+
+#[test]
+fn approved_leave_within_range() {
+    let launch = mock_launch().env("MOCK_LEAVE", "2026-10-02:full,2026-10-05:full,2026-10-06:morning");
+    let mut worker = PlaywrightWorker::with_launch(launch);
+    let value = worker
+        .send_command(
+            "getApprovedLeave",
+            json!({ "from": "2026-10-05", "to": "2026-10-11" }),
+        )
+        .unwrap();
+
+    let days: Vec<LeaveDay> = serde_json::from_value(value).unwrap();
+    assert_eq!(days.len(), 2);
+    assert_eq!(days[1].period, LeavePeriod::Morning);
+}
+
+#[test]
+fn failed_action_is_response_error() {
+    let mut worker = mock_worker();
+    match worker.send_command("noSuchAction", json!({})) {
+        Err(PlaywrightError::ResponseError(msg)) => assert!(msg.contains("noSuchAction")),
+        other => panic!("unexpected: {:?}", other),
+    }
+}
+
+#[test]
+fn failed_action_returns_capture() {
+    let dir = state_dir("capture");
+    let mut worker = mock_worker();
+    worker.set_capture_dir(dir.clone());
+
+    let err = worker
+        .send_command("noSuchAction", json!({ "capture": { "keep": 5 } }))
+        .unwrap_err();
+    assert!(err.to_string().contains("noSuchAction"));
+    let capture = err.capture().expect("capture");
+    assert_eq!(capture.screenshot, dir.join("noSuchAction.png"));
+    assert!(capture.html.exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn timeout_restarts_worker() {
+    let mut worker = mock_worker();
+    worker.set_timeout("__hang", Duration::from_millis(300));
+
+    match worker.send_command("__hang", json!({})) {
+        Err(PlaywrightError::Timeout { action, .. }) => assert_eq!(action, "__hang"),
+        other => panic!("unexpected: {:?}", other),
+    }
+
+    // 응답 없던 워커 대신 새 워커가 다음 요청을 처리
+    assert!(worker.send_command("isLoggedIn", json!({})).is_ok());
+}
+
+#[test]
+fn timeout_applies_only_to_its_action() {
+    let mut worker = mock_worker();
+    worker.set_timeout("__sleep", Duration::from_millis(200));
+
+    assert_eq!(worker.timeout_for("__sleep"), Duration::from_millis(200));
+    assert!(worker.timeout_for("getStatus") > Duration::from_secs(1));
+    assert!(matches!(
+        worker.send_command("__sleep", json!({ "ms": 1000 })),
+        Err(PlaywrightError::Timeout { .. })
+    ));
+    // 다른 액션은 기본 대기 시간으로 처리
+    assert!(worker.send_command("getStatus", json!({})).is_ok());
+}
+
+#[test]
+fn malformed_output_times_out() {
+    let mut worker = mock_worker();
+    worker.set_timeout("__garbage", Duration::from_millis(300));
+
+    assert!(matches!(
+        worker.send_command("__garbage", json!({})),
+        Err(PlaywrightError::Timeout { .. })
+    ));
+}
+
+#[test]
+fn slow_reply_within_timeout() {
+    let mut worker = mock_worker();
+    worker.set_timeout("__sleep", Duration::from_secs(5));
+    assert!(worker.send_command("__sleep", json!({ "ms": 200 })).is_ok());
+}
+
+#[test]
+fn idempotent_action_is_replayed_after_crash() {
+    let dir = state_dir("replay");
+    let launch = mock_launch()
+        .env("MOCK_CRASH_ONCE", "getStatus")
+        .env("MOCK_STATE_DIR", dir.to_string_lossy());
+    let mut worker = PlaywrightWorker::with_launch(launch);
+
+    assert!(worker.send_command("getStatus", json!({})).is_ok());
+    assert!(dir.join("crashed-getStatus").exists());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn non_idempotent_crash_is_reported() {
+    let mut worker = mock_worker();
+
+    assert!(matches!(
+        worker.send_command("__crash", json!({})),
+        Err(PlaywrightError::Crashed)
+    ));
+    assert!(worker.send_command("getStatus", json!({})).is_ok());
+}
+
+fn starts(dir: &std::path::Path) -> usize {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with("start-"))
+        .count()
+}
+
+#[test]
+fn start_is_retried_with_backoff() {
+    let dir = state_dir("retry");
+    let launch = mock_launch()
+        .env("MOCK_FAIL_STARTS", "2")
+        .env("MOCK_STATE_DIR", dir.to_string_lossy());
+    let mut worker = PlaywrightWorker::with_launch(launch);
+
+    // 두 번 실패한 뒤 세 번째 시도에서 시작
+    let started = std::time::Instant::now();
+    assert!(worker.send_command("getStatus", json!({})).is_ok());
+    assert_eq!(starts(&dir), 3);
+    // 0.5초 + 1초 간격
+    assert!(started.elapsed() >= Duration::from_millis(1500));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn start_gives_up_after_max_attempts() {
+    let dir = state_dir("give-up");
+    let launch = mock_launch()
+        .env("MOCK_FAIL_STARTS", "10")
+        .env("MOCK_STATE_DIR", dir.to_string_lossy());
+    let mut worker = PlaywrightWorker::with_launch(launch);
+
+    assert!(matches!(
+        worker.send_command("getStatus", json!({})),
+        Err(PlaywrightError::ResponseError(_))
+    ));
+    assert_eq!(starts(&dir), 3);
+    assert!(!worker.is_alive());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn dead_worker_is_detected_and_respawned() {
+    let mut worker = mock_worker();
+    worker.start().unwrap();
+    assert!(worker.is_alive());
+
+    let _ = worker.send_command("__crash", json!({}));
+    assert!(!worker.is_alive());
+    // 다음 요청에서 새 프로세스를 띄움
+    assert!(worker.send_command("isLoggedIn", json!({})).is_ok());
+    assert!(worker.is_alive());
+}
+
+#[test]
+fn stray_lines_and_stale_ids_are_skipped() {
+    let mut worker = PlaywrightWorker::with_launch(mock_launch().env("MOCK_NOISY", "1"));
+
+    let reply: ActionReply =
+        serde_json::from_value(worker.send_command("setMeeting", json!({})).unwrap()).unwrap();
+    assert_eq!(reply.message, "회의 상태로 변경됨");
+
+    let status: AttendanceStatus =
+        serde_json::from_value(worker.send_command("getStatus", json!({})).unwrap()).unwrap();
+    assert_eq!(status.status.as_deref(), Some("회의"));
+}
+
+#[test]
+fn not_ready_fails_to_start() {
+    let mut worker = PlaywrightWorker::with_launch(mock_launch().env("MOCK_NOT_READY", "1"));
+    assert!(matches!(
+        worker.start(),
+        Err(PlaywrightError::ResponseError(_))
+    ));
+}
+
+#[test]
+fn missing_node_is_spawn_error() {
+    let launch = WorkerLaunch::new("/nonexistent/node", "mock-worker.js");
+    let mut worker = PlaywrightWorker::with_launch(launch);
+    assert!(matches!(
+        worker.send_command("getStatus", json!({})),
+        Err(PlaywrightError::SpawnError(_))
+    ));
+}
@@ -5,6 +5,10 @@ const elements = {
   username: document.getElementById("username"),
   password: document.getElementById("password"),
   saveBtn: document.getElementById("save-btn"),
+  exportFrom: document.getElementById("export-from"),
+  exportTo: document.getElementById("export-to"),
+  exportCsvBtn: document.getElementById("export-csv-btn"),
+  exportIcsBtn: document.getElementById("export-ics-btn"),
   message: document.getElementById("message"),
 };
 
@@ -66,9 +70,53 @@ async function saveSettings() {
   }
 }
 
+function formatDate(date) {
+  const y = date.getFullYear();
+  const m = String(date.getMonth() + 1).padStart(2, "0");
+  const d = String(date.getDate()).padStart(2, "0");
+  return `${y}-${m}-${d}`;
+}
+
+// 기본 기간은 이번 달 1일부터 오늘까지
+function initExportRange() {
+  const today = new Date();
+  elements.exportFrom.value = formatDate(
+    new Date(today.getFullYear(), today.getMonth(), 1),
+  );
+  elements.exportTo.value = formatDate(today);
+}
+
+async function exportHistory(format, button) {
+  const from = elements.exportFrom.value;
+  const to = elements.exportTo.value;
+
+  if (!from || !to) {
+    showMessage("기간을 선택해주세요", "error");
+    return;
+  }
+
+  button.disabled = true;
+
+  try {
+    const path = await invoke("export_history", { format, from, to });
+    showMessage(`저장됨: ${path}`, "success");
+  } catch (e) {
+    showMessage(`오류: ${e}`, "error");
+  } finally {
+    button.disabled = false;
+  }
+}
+
 async function init() {
   await loadSettings();
+  initExportRange();
   elements.saveBtn.addEventListener("click", saveSettings);
+  elements.exportCsvBtn.addEventListener("click", () =>
+    exportHistory("csv", elements.exportCsvBtn),
+  );
+  elements.exportIcsBtn.addEventListener("click", () =>
+    exportHistory("ics", elements.exportIcsBtn),
+  );
 }
 
 document.addEventListener("DOMContentLoaded", init);